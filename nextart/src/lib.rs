@@ -0,0 +1,3888 @@
+//! Indexing and box art management behind the NextArt GUI, for other frontends and automation.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ffi::{OsStr, OsString},
+    fs::{DirEntry, File},
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use ::image::{
+    AnimationDecoder, DynamicImage, ImageError, ImageFormat, ImageReader, RgbaImage,
+    codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder},
+    error::{ImageFormatHint, UnsupportedErrorKind},
+    imageops::FilterType,
+};
+use bittenhumans::ByteSizeFormatter;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+pub mod strings;
+
+const CONFIG_FILE_NAME: &str = "config.json";
+/// Hand-written alternative to `CONFIG_FILE_NAME`, read and saved in its place when present.
+const CONFIG_TOML_FILE_NAME: &str = "config.toml";
+const COVERAGE_FILE_NAME: &str = "coverage.json";
+/// Folder in the config folder that persisted gallery thumbnails are kept in.
+const THUMBNAIL_CACHE_DIR_NAME: &str = "thumbnails";
+const IMPORT_JOURNAL_FILE_NAME: &str = "import-journal.json";
+/// Art written between saves of the import journal, which is rewritten whole each time.
+pub const IMPORT_JOURNAL_SAVE_INTERVAL: usize = 25;
+const CONFIG_DIR_ENV_VAR: &str = "NEXTART_CONFIG_DIR";
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+const DOWNLOAD_MAX_BYTES: u64 = 50 * 1024 * 1024;
+/// Extensions tried, in order, when looking a ROM's art up in a local art source folder.
+const ART_SOURCE_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+/// Stands for the ROM name in the URL of an HTTP art source.
+const ART_SOURCE_NAME_PLACEHOLDER: &str = "{name}";
+const FS_ATTEMPTS: u32 = 2;
+const SLOW_FS_WARNING: Duration = Duration::from_secs(2);
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Characters that aren't allowed in folder names on the FAT file systems SD cards use.
+const INVALID_NAME_CHARACTERS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+const BOXART_TEMPLATE_STEM: &str = "{stem}";
+pub const DEFAULT_BOXART_TEMPLATE: &str = "{stem}.png";
+const NOTE_FILE_SUFFIX: &str = ".note.txt";
+const PREVIOUS_ART_INFIX: &str = ".prev";
+/// Optional file in a collection's media folder that overrides art settings for that collection.
+const COLLECTION_MANIFEST_FILE_NAME: &str = "manifest.json";
+/// File in a collection folder marking it as one that never gets art, such as a tools folder.
+const NO_MEDIA_MARKER_FILE_NAME: &str = ".nomedia";
+/// File in a collection folder where EmulationStation-style frontends keep ROM names and art paths.
+const GAMELIST_FILE_NAME: &str = "gamelist.xml";
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Index {
+    /// May hold ROMs that no collection lists anymore after a collection was rescanned.
+    pub roms: Vec<Rom>,
+    pub collections: Vec<Collection>,
+}
+
+impl Index {
+    /// ROMs that are listed in a collection, with their index.
+    pub fn listed_roms(&self) -> impl Iterator<Item = (usize, &Rom)> {
+        self.collections
+            .iter()
+            .flat_map(|x| &x.rom_indices)
+            .filter_map(|x| Some((*x, self.roms.get(*x)?)))
+    }
+
+    /// Recomputes every ROM's `display_name`, after the normalization setting changed.
+    pub fn normalize_names(&mut self, normalization: NameNormalization) {
+        for rom in &mut self.roms {
+            rom.display_name = normalization.apply(&rom.name);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Rom {
+    /// File stem, which art, notes and settings are keyed by.
+    pub name: String,
+    /// Name shown, sorted and searched by: `name` cleaned up by the name normalization setting.
+    pub display_name: String,
+    #[serde(serialize_with = "serialize_path_lossy")]
+    pub boxart_path: PathBuf,
+    pub boxart_size: u64,
+    /// When the box art file was last written, if known.
+    pub boxart_modified: Option<SystemTime>,
+    /// Raw file stem, for rebuilding `boxart_path` when the naming template changes.
+    #[serde(skip)]
+    pub stem: OsString,
+    /// Free-text note the user attached, stored in a sidecar file next to the box art.
+    pub note: String,
+    /// Whether the box art's color type has an alpha channel, known once it has been decoded.
+    #[serde(skip)]
+    pub boxart_has_alpha: Option<bool>,
+    /// Number of frames in the box art, known once it has been decoded. Static art has one.
+    #[serde(skip)]
+    pub boxart_frames: Option<usize>,
+    /// Width and height of the box art, read from its header while indexing when
+    /// `read_art_dimensions` is on, or known once it has been decoded.
+    pub boxart_dimensions: Option<(u32, u32)>,
+}
+
+impl Rom {
+    /// A ROM with no art or note known yet, named after its raw file stem.
+    pub fn new(stem: &OsStr, display_name: String, boxart_path: PathBuf) -> Self {
+        Self {
+            name: stem.to_string_lossy().into(),
+            display_name,
+            boxart_path,
+            boxart_size: 0,
+            boxart_modified: None,
+            stem: stem.to_os_string(),
+            note: String::new(),
+            boxart_has_alpha: None,
+            boxart_frames: None,
+            boxart_dimensions: None,
+        }
+    }
+
+    pub fn note_path(&self) -> PathBuf {
+        let mut file_name = self.stem.clone();
+        file_name.push(NOTE_FILE_SUFFIX);
+        self.boxart_path.with_file_name(file_name)
+    }
+
+    pub fn previous_art_path(&self) -> PathBuf {
+        previous_art_path(&self.boxart_path)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistentConfig {
+    pub roms_path: PathBuf,
+    #[serde(default)]
+    pub size_units: SizeUnits,
+    /// Collections the user has marked as never expected to have box art.
+    #[serde(default)]
+    pub art_exempt_collections: BTreeSet<String>,
+    /// Collections listed before all others, in this order.
+    #[serde(default)]
+    pub pinned_collections: Vec<String>,
+    #[serde(default)]
+    pub import_resize: ImportResize,
+    #[serde(default)]
+    pub resize_filter: ResizeFilter,
+    #[serde(default)]
+    pub png_color_type: PngColorType,
+    #[serde(default)]
+    pub density: ListDensity,
+    #[serde(default)]
+    pub rom_layout: RomLayout,
+    /// Share of the ROM list view's width taken by the list, the info panel getting the rest.
+    #[serde(default = "PersistentConfig::default_pane_ratio")]
+    pub pane_ratio: f32,
+    #[serde(default)]
+    pub art_transparency: ArtTransparency,
+    #[serde(default)]
+    pub theme: AppTheme,
+    #[serde(default)]
+    pub ui_scale: UiScale,
+    /// Ask before copying images larger than this to the clipboard.
+    #[serde(default)]
+    pub clipboard_warn_megapixels: Megapixels,
+    /// File name of a ROM's box art inside `.media`, with `{stem}` standing in for the ROM's file stem.
+    #[serde(default = "PersistentConfig::default_boxart_template")]
+    pub boxart_template: String,
+    #[serde(default)]
+    pub preview_max_size: PreviewSize,
+    #[serde(default)]
+    pub preview_guides: PreviewGuides,
+    #[serde(default)]
+    pub thumbnail_cache_limit: ThumbnailCacheLimit,
+    /// Keep gallery thumbnails in the config folder so they aren't decoded again next launch.
+    #[serde(default)]
+    pub persist_thumbnails: bool,
+    /// Keeps box art in `{art_root}/{collection}/` instead of each collection's `.media` folder.
+    #[serde(default)]
+    pub art_root: Option<PathBuf>,
+    /// Image to fill in for ROMs without box art, keyed by collection name.
+    #[serde(default)]
+    pub collection_default_art: BTreeMap<String, PathBuf>,
+    /// Names of ROMs whose box art the user has reviewed, keyed by collection name.
+    #[serde(default)]
+    pub verified_roms: BTreeMap<String, BTreeSet<String>>,
+    /// Gives up on filesystem calls that hang, e.g. on a network-mounted SD card.
+    #[serde(default)]
+    pub fs_timeout: FsTimeout,
+    #[serde(default)]
+    pub read_buffer_size: ReadBufferSize,
+    #[serde(default)]
+    pub media_folder_creation: MediaFolderCreation,
+    #[serde(default)]
+    pub name_normalization: NameNormalization,
+    #[serde(default)]
+    pub default_rom_action: DefaultRomAction,
+    /// Some apps mishandle transparency when pasting, so images can be flattened before copying.
+    #[serde(default)]
+    pub clipboard_alpha: ClipboardAlpha,
+    /// Hide collections that have all their box art, or aren't expected to have any.
+    #[serde(default)]
+    pub only_incomplete_collections: bool,
+    /// Folder the image picker opens in, i.e. where art was last picked from.
+    #[serde(default)]
+    pub last_image_dir: Option<PathBuf>,
+    /// Patterns of collection folder names that are never indexed, with `*` and `?` wildcards.
+    #[serde(default)]
+    pub excluded_collections: Vec<String>,
+    /// Split a Roms folder that holds ROMs directly into one collection per system.
+    #[serde(default)]
+    pub group_by_extension: bool,
+    /// System names keyed by lowercase file extension, used by `group_by_extension`.
+    #[serde(default = "PersistentConfig::default_extension_systems")]
+    pub extension_systems: BTreeMap<String, String>,
+    /// Categories picked by hand, keyed by collection name. Others are guessed from the system.
+    #[serde(default)]
+    pub collection_categories: BTreeMap<String, String>,
+    /// Categories keyed by uppercase system tag, used to guess a collection's category.
+    #[serde(default = "PersistentConfig::default_system_categories")]
+    pub system_categories: BTreeMap<String, String>,
+    /// Only list collections in this category. Empty lists all of them.
+    #[serde(default)]
+    pub category_filter: String,
+    /// Brightness and contrast last applied to replacement art, used as the starting point next time.
+    #[serde(default)]
+    pub art_adjustment: ArtAdjustment,
+    /// Never write to disk, for inspecting a library without risk of changing it.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Program and arguments to open box art with. Empty uses the system's default image app.
+    #[serde(default)]
+    pub editor_command: String,
+    /// List collection folders whose names differ only in case as a single collection.
+    #[serde(default)]
+    pub merge_case_duplicates: bool,
+    /// Keep the art a write replaces next to it as `{stem}.prev.png`, on top of the undo copies.
+    #[serde(default)]
+    pub keep_previous_art: bool,
+    /// Read the dimensions of all box art from the image headers while indexing.
+    #[serde(default)]
+    pub read_art_dimensions: bool,
+    /// Take ROM names and box art paths from a collection's `gamelist.xml` where it has one.
+    #[serde(default)]
+    pub read_gamelists: bool,
+    /// Where Fetch Art looks up art by ROM name.
+    #[serde(default)]
+    pub art_source: ArtSourceKind,
+    /// Folder for a local art source, or URL with `{name}` for an HTTP one.
+    #[serde(default)]
+    pub art_source_location: String,
+}
+
+impl Default for PersistentConfig {
+    fn default() -> Self {
+        Self {
+            roms_path: PathBuf::new(),
+            size_units: SizeUnits::default(),
+            art_exempt_collections: BTreeSet::new(),
+            pinned_collections: Vec::new(),
+            import_resize: ImportResize::default(),
+            resize_filter: ResizeFilter::default(),
+            png_color_type: PngColorType::default(),
+            density: ListDensity::default(),
+            rom_layout: RomLayout::default(),
+            pane_ratio: Self::default_pane_ratio(),
+            art_transparency: ArtTransparency::default(),
+            theme: AppTheme::default(),
+            ui_scale: UiScale::default(),
+            clipboard_warn_megapixels: Megapixels::default(),
+            boxart_template: Self::default_boxart_template(),
+            preview_max_size: PreviewSize::default(),
+            preview_guides: PreviewGuides::default(),
+            thumbnail_cache_limit: ThumbnailCacheLimit::default(),
+            persist_thumbnails: false,
+            art_root: None,
+            collection_default_art: BTreeMap::new(),
+            verified_roms: BTreeMap::new(),
+            fs_timeout: FsTimeout::default(),
+            read_buffer_size: ReadBufferSize::default(),
+            media_folder_creation: MediaFolderCreation::default(),
+            name_normalization: NameNormalization::default(),
+            default_rom_action: DefaultRomAction::default(),
+            clipboard_alpha: ClipboardAlpha::default(),
+            only_incomplete_collections: false,
+            last_image_dir: None,
+            excluded_collections: Vec::new(),
+            group_by_extension: false,
+            extension_systems: Self::default_extension_systems(),
+            collection_categories: BTreeMap::new(),
+            system_categories: Self::default_system_categories(),
+            category_filter: String::new(),
+            art_adjustment: ArtAdjustment::default(),
+            read_only: false,
+            editor_command: String::new(),
+            merge_case_duplicates: false,
+            keep_previous_art: false,
+            read_art_dimensions: false,
+            read_gamelists: false,
+            art_source: ArtSourceKind::default(),
+            art_source_location: String::new(),
+        }
+    }
+}
+
+impl PersistentConfig {
+    /// The art source Fetch Art uses, if one is chosen and has a location.
+    pub fn art_source(&self) -> Option<Arc<dyn ArtSource>> {
+        let location = self.art_source_location.trim();
+        if location.is_empty() {
+            return None;
+        }
+        match self.art_source {
+            ArtSourceKind::None => None,
+            ArtSourceKind::LocalFolder => Some(Arc::new(LocalFolderSource {
+                folder: PathBuf::from(location),
+            })),
+            ArtSourceKind::Http => Some(Arc::new(HttpSource {
+                url_template: location.to_owned(),
+            })),
+        }
+    }
+
+    pub fn default_boxart_template() -> String {
+        String::from(DEFAULT_BOXART_TEMPLATE)
+    }
+
+    pub fn default_pane_ratio() -> f32 {
+        0.5
+    }
+
+    pub fn default_extension_systems() -> BTreeMap<String, String> {
+        [
+            ("32x", "32X"),
+            ("gb", "GB"),
+            ("gba", "GBA"),
+            ("gbc", "GBC"),
+            ("gen", "MD"),
+            ("gg", "GG"),
+            ("md", "MD"),
+            ("n64", "N64"),
+            ("nes", "FC"),
+            ("pce", "PCE"),
+            ("sfc", "SFC"),
+            ("smc", "SFC"),
+            ("sms", "MS"),
+            ("z64", "N64"),
+        ]
+        .into_iter()
+        .map(|(extension, system)| (extension.to_owned(), system.to_owned()))
+        .collect()
+    }
+
+    pub fn extension_system(&self, extension: &OsStr) -> Option<&str> {
+        self.extension_systems
+            .get(&extension.to_string_lossy().to_lowercase())
+            .map(String::as_str)
+    }
+
+    pub fn default_system_categories() -> BTreeMap<String, String> {
+        [
+            (
+                "Handheld",
+                &[
+                    "GB", "GBA", "GBC", "GG", "LYNX", "NDS", "NGP", "NGPC", "PKM", "PSP", "VB",
+                    "WS", "WSC",
+                ][..],
+            ),
+            (
+                "Console",
+                &[
+                    "32X", "A2600", "A7800", "FC", "MD", "MS", "N64", "PCE", "PS", "SEGACD", "SFC",
+                ][..],
+            ),
+            (
+                "Arcade",
+                &["CPS1", "CPS2", "CPS3", "FBN", "MAME", "NEOGEO"][..],
+            ),
+        ]
+        .into_iter()
+        .flat_map(|(category, systems)| {
+            systems
+                .iter()
+                .map(move |system| (system.to_string(), category.to_owned()))
+        })
+        .collect()
+    }
+
+    /// The category picked for a collection, or else the one its system is in. The system is the
+    /// tag at the end of the name, like `GBA` in `Game Boy Advance (GBA)`, or the whole name.
+    pub fn collection_category(&self, collection_name: &str) -> Option<&str> {
+        if let Some(category) = self.collection_categories.get(collection_name) {
+            return Some(category);
+        }
+        let system = collection_name
+            .trim_end()
+            .strip_suffix(')')
+            .and_then(|x| x.rsplit_once('('))
+            .map_or(collection_name, |(_, tag)| tag);
+        self.system_categories
+            .get(&system.trim().to_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Every category a collection can be put in.
+    pub fn categories(&self) -> BTreeSet<&str> {
+        self.system_categories
+            .values()
+            .chain(self.collection_categories.values())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Formats `system_categories` the way the settings field accepts it.
+    pub fn system_categories_text(&self) -> String {
+        self.system_categories
+            .iter()
+            .map(|(system, category)| format!("{system}={category}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Formats `extension_systems` the way the settings field accepts it.
+    pub fn extension_systems_text(&self) -> String {
+        self.extension_systems
+            .iter()
+            .map(|(extension, system)| format!("{extension}={system}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The template must name an image format art can be saved in, since the extension picks
+    /// the encoder.
+    pub fn is_valid_boxart_template(template: &str) -> bool {
+        template.contains(BOXART_TEMPLATE_STEM)
+            && !template.contains(['/', '\\'])
+            && !template.contains("..")
+            && ImageFormat::from_path(template).is_ok_and(|x| x.writing_enabled())
+    }
+
+    pub fn boxart_file_name(&self, stem: &OsStr) -> OsString {
+        let mut file_name = OsString::new();
+        for (i, part) in self.boxart_template.split(BOXART_TEMPLATE_STEM).enumerate() {
+            if i > 0 {
+                file_name.push(stem);
+            }
+            file_name.push(part);
+        }
+        file_name
+    }
+
+    /// Puts back the default template when a config file written outside the app holds one that
+    /// `is_valid_boxart_template` rejects, since it could send art writes out of the media folder.
+    fn with_valid_boxart_template(mut self) -> Self {
+        if !Self::is_valid_boxart_template(&self.boxart_template) {
+            log::warn!(
+                "Ignoring invalid box art template '{}'",
+                self.boxart_template
+            );
+            self.boxart_template = Self::default_boxart_template();
+        }
+        self
+    }
+
+    /// Writes the settings to a file of the user's choosing, for backup or moving to another machine.
+    pub fn export_to(&self, path: &Path) -> Result<(), String> {
+        let serialized = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_SETTINGS_EXPORT, e))?;
+        std::fs::write(path, serialized).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_SETTINGS_EXPORT,
+                write_error_detail(&e)
+            )
+        })
+    }
+
+    pub fn import_from(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_SETTINGS_IMPORT, e))?;
+        serde_json::from_str(&content)
+            .map(Self::with_valid_boxart_template)
+            .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_SETTINGS_IMPORT, e))
+    }
+
+    /// Whether writes are disabled, by the setting or for this launch by `--read-only`.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only || READ_ONLY_LAUNCH.load(Ordering::Relaxed)
+    }
+
+    pub fn is_excluded_collection(&self, folder_name: &str) -> bool {
+        self.excluded_collections
+            .iter()
+            .any(|pattern| glob_matches(pattern, folder_name))
+    }
+
+    pub fn is_verified(&self, collection_name: &str, rom_name: &str) -> bool {
+        self.verified_roms
+            .get(collection_name)
+            .is_some_and(|x| x.contains(rom_name))
+    }
+
+    /// Reads the config file, returning `None` if none has been written yet. A `config.toml`
+    /// takes precedence over `config.json`, and changes made in the app are then saved to it.
+    pub fn load() -> Result<Option<Self>, String> {
+        let config_dir = config_dir()?;
+
+        match std::fs::read_to_string(config_dir.join(CONFIG_TOML_FILE_NAME)) {
+            Ok(content) => {
+                log::info!("Reading settings from {CONFIG_TOML_FILE_NAME}");
+                return toml::from_str::<PersistentConfig>(&content)
+                    .map(|x| Some(x.with_valid_boxart_template()))
+                    .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_CONFIG_TOML_READ, e));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(format!("{}: {}", strings::ERROR_PREFIX_CONFIG_TOML_READ, e));
+            }
+        }
+
+        match std::fs::read_to_string(config_dir.join(CONFIG_FILE_NAME)) {
+            Ok(content) => serde_json::from_str::<PersistentConfig>(&content)
+                .map(|x| Some(x.with_valid_boxart_template()))
+                .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_CONFIG_FILE_READ, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {}", strings::ERROR_PREFIX_CONFIG_FILE_READ, e)),
+        }
+    }
+
+    /// Writes the config file, unless read-only mode is on.
+    pub fn save(&self) -> Result<(), String> {
+        if self.is_read_only() {
+            return Ok(());
+        }
+        self.write()
+    }
+
+    /// Writes the config file even in read-only mode, so turning the setting on persists.
+    pub fn write(&self) -> Result<(), String> {
+        let config_dir = config_dir()?;
+
+        std::fs::create_dir_all(&config_dir).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_CONFIG_DIR_CREATE,
+                write_error_detail(&e)
+            )
+        })?;
+
+        // Written back to the file `load` reads, so hand-written settings don't shadow changes.
+        let toml_path = config_dir.join(CONFIG_TOML_FILE_NAME);
+        let (path, serialized) = if toml_path.is_file() {
+            let serialized = toml::to_string_pretty(self)
+                .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_CONFIG_FILE_CREATE, e))?;
+            (toml_path, serialized)
+        } else {
+            let serialized = serde_json::to_string(self)
+                .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_CONFIG_FILE_CREATE, e))?;
+            (config_dir.join(CONFIG_FILE_NAME), serialized)
+        };
+
+        std::fs::write(path, serialized).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_CONFIG_FILE_CREATE,
+                write_error_detail(&e)
+            )
+        })
+    }
+}
+
+/// Folder holding config.json and other app state, which `NEXTART_CONFIG_DIR` can override for
+/// portable installs or separate profiles.
+pub fn config_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV_VAR).filter(|x| !x.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    ProjectDirs::from("", strings::DIR_ORG, strings::DIR_APP)
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .ok_or_else(|| strings::ERROR_NO_HOME_DIRECTORY.to_owned())
+}
+
+/// Folder persisted gallery thumbnails go in: the system's cache folder, so they aren't backed up
+/// or synced with settings, or inside the config folder when `NEXTART_CONFIG_DIR` overrides it.
+fn thumbnail_cache_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = std::env::var_os(CONFIG_DIR_ENV_VAR).filter(|x| !x.is_empty()) {
+        return Ok(PathBuf::from(dir).join(THUMBNAIL_CACHE_DIR_NAME));
+    }
+
+    ProjectDirs::from("", strings::DIR_ORG, strings::DIR_APP)
+        .map(|dirs| dirs.cache_dir().join(THUMBNAIL_CACHE_DIR_NAME))
+        .ok_or_else(|| strings::ERROR_NO_HOME_DIRECTORY.to_owned())
+}
+
+/// Missing art counts per collection, saved when the app closes so the next session can show progress.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CoverageSnapshot {
+    pub missing_art: BTreeMap<String, usize>,
+}
+
+impl CoverageSnapshot {
+    pub fn from_index(index: &Index) -> Self {
+        Self {
+            missing_art: index
+                .collections
+                .iter()
+                .map(|x| (x.name.clone(), x.missing_art_count(&index.roms)))
+                .collect(),
+        }
+    }
+
+    pub fn load() -> Result<Option<Self>, String> {
+        let coverage_file = config_dir()?.join(COVERAGE_FILE_NAME);
+
+        match std::fs::read_to_string(&coverage_file) {
+            Ok(content) => serde_json::from_str::<CoverageSnapshot>(&content)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_COVERAGE_FILE_READ, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_COVERAGE_FILE_READ,
+                e
+            )),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let config_dir = config_dir()?;
+
+        std::fs::create_dir_all(&config_dir).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_CONFIG_DIR_CREATE,
+                write_error_detail(&e)
+            )
+        })?;
+
+        let serialized = serde_json::to_string(self)
+            .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_COVERAGE_FILE_CREATE, e))?;
+
+        std::fs::write(config_dir.join(COVERAGE_FILE_NAME), serialized).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_COVERAGE_FILE_CREATE,
+                write_error_detail(&e)
+            )
+        })
+    }
+
+    /// Art added to `collection` since this snapshot was taken; negative if art went missing.
+    pub fn art_added(&self, collection: &Collection, roms: &[Rom]) -> Option<isize> {
+        self.missing_art
+            .get(&collection.name)
+            .map(|previous| *previous as isize - collection.missing_art_count(roms) as isize)
+    }
+}
+
+/// Art already fetched by an interrupted Fetch Art run, so running it again for the same ROMs from
+/// the same source skips what's done instead of starting over. Removed once a run finishes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ImportJournal {
+    pub source: ArtSourceKind,
+    pub location: String,
+    /// Box art paths of every ROM the run was started for.
+    #[serde(default)]
+    pub targets: BTreeSet<PathBuf>,
+    pub fetched: BTreeSet<PathBuf>,
+}
+
+impl ImportJournal {
+    /// The journal left by an interrupted run for `targets` from the same source, or an empty one.
+    pub fn resume(source: ArtSourceKind, location: &str, targets: BTreeSet<PathBuf>) -> Self {
+        match Self::load() {
+            Ok(Some(journal))
+                if journal.source == source
+                    && journal.location == location
+                    && journal.targets == targets =>
+            {
+                log::info!(
+                    "Resuming an interrupted import, {} ROMs already have art",
+                    journal.fetched.len()
+                );
+                journal
+            }
+            result => {
+                if let Err(e) = result {
+                    log::warn!("{e}");
+                }
+                Self {
+                    source,
+                    location: location.to_owned(),
+                    targets,
+                    fetched: BTreeSet::new(),
+                }
+            }
+        }
+    }
+
+    /// Whether the art for `boxart_path` was fetched earlier and is still there. Art that was
+    /// undone or deleted since is fetched again.
+    pub fn is_done(&self, boxart_path: &Path) -> bool {
+        self.fetched.contains(boxart_path) && boxart_path.exists()
+    }
+
+    pub fn load() -> Result<Option<Self>, String> {
+        let journal_file = config_dir()?.join(IMPORT_JOURNAL_FILE_NAME);
+
+        match std::fs::read_to_string(&journal_file) {
+            Ok(content) => serde_json::from_str::<ImportJournal>(&content)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_IMPORT_JOURNAL_READ, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_IMPORT_JOURNAL_READ,
+                e
+            )),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let config_dir = config_dir()?;
+
+        std::fs::create_dir_all(&config_dir).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_CONFIG_DIR_CREATE,
+                write_error_detail(&e)
+            )
+        })?;
+
+        let serialized = serde_json::to_string(self)
+            .map_err(|e| format!("{}: {}", strings::ERROR_PREFIX_IMPORT_JOURNAL_WRITE, e))?;
+
+        std::fs::write(config_dir.join(IMPORT_JOURNAL_FILE_NAME), serialized).map_err(|e| {
+            format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_IMPORT_JOURNAL_WRITE,
+                write_error_detail(&e)
+            )
+        })
+    }
+
+    pub fn remove() -> Result<(), String> {
+        match std::fs::remove_file(config_dir()?.join(IMPORT_JOURNAL_FILE_NAME)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(format!(
+                "{}: {}",
+                strings::ERROR_PREFIX_IMPORT_JOURNAL_WRITE,
+                write_error_detail(&e)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SizeUnits {
+    #[default]
+    Binary,
+    Decimal,
+}
+
+impl SizeUnits {
+    pub const ALL: [SizeUnits; 2] = [SizeUnits::Binary, SizeUnits::Decimal];
+
+    pub fn system(self) -> bittenhumans::consts::System {
+        match self {
+            SizeUnits::Binary => bittenhumans::consts::System::Binary,
+            SizeUnits::Decimal => bittenhumans::consts::System::Decimal,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListDensity {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl ListDensity {
+    pub const ALL: [ListDensity; 2] = [ListDensity::Comfortable, ListDensity::Compact];
+}
+
+impl std::fmt::Display for ListDensity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ListDensity::Comfortable => strings::LABEL_DENSITY_COMFORTABLE,
+            ListDensity::Compact => strings::LABEL_DENSITY_COMPACT,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RomLayout {
+    #[default]
+    List,
+    /// Thumbnails of the box art in a grid.
+    Gallery,
+}
+
+impl RomLayout {
+    pub const ALL: [RomLayout; 2] = [RomLayout::List, RomLayout::Gallery];
+}
+
+impl std::fmt::Display for RomLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RomLayout::List => strings::LABEL_LAYOUT_LIST,
+            RomLayout::Gallery => strings::LABEL_LAYOUT_GALLERY,
+        })
+    }
+}
+
+/// What the frontend on the device expects of box art, so art that will look wrong there is flagged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArtTransparency {
+    #[default]
+    Any,
+    /// The frontend draws its own background behind the art.
+    Transparent,
+    /// The frontend shows the art as is, so transparent areas look unfinished.
+    Opaque,
+}
+
+impl ArtTransparency {
+    pub const ALL: [ArtTransparency; 3] = [
+        ArtTransparency::Any,
+        ArtTransparency::Transparent,
+        ArtTransparency::Opaque,
+    ];
+
+    /// Warning for art whose alpha channel doesn't suit the frontend, if any.
+    pub fn warning(self, has_alpha: bool) -> Option<&'static str> {
+        match (self, has_alpha) {
+            (ArtTransparency::Transparent, false) => Some(strings::LABEL_ART_NO_ALPHA),
+            (ArtTransparency::Opaque, true) => Some(strings::LABEL_ART_HAS_ALPHA),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ArtTransparency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ArtTransparency::Any => strings::LABEL_TRANSPARENCY_ANY,
+            ArtTransparency::Transparent => strings::LABEL_TRANSPARENCY_TRANSPARENT,
+            ArtTransparency::Opaque => strings::LABEL_TRANSPARENCY_OPAQUE,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AppTheme {
+    /// Follows the operating system's light or dark mode.
+    #[default]
+    System,
+    Light,
+    Dark,
+    /// Pure black and white with saturated accents and larger text.
+    HighContrast,
+}
+
+impl AppTheme {
+    pub const ALL: [AppTheme; 4] = [
+        AppTheme::System,
+        AppTheme::Light,
+        AppTheme::Dark,
+        AppTheme::HighContrast,
+    ];
+
+    /// Multiplier for all text and spacing, so themes meant for low vision can enlarge the UI.
+    pub fn scale_factor(self) -> f64 {
+        match self {
+            AppTheme::HighContrast => 1.25,
+            _ => 1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for AppTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            AppTheme::System => strings::LABEL_THEME_SYSTEM,
+            AppTheme::Light => strings::LABEL_THEME_LIGHT,
+            AppTheme::Dark => strings::LABEL_THEME_DARK,
+            AppTheme::HighContrast => strings::LABEL_THEME_HIGH_CONTRAST,
+        })
+    }
+}
+
+/// Size of the whole UI, on top of whatever the theme asks for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UiScale {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl UiScale {
+    pub const ALL: [UiScale; 3] = [UiScale::Small, UiScale::Medium, UiScale::Large];
+
+    pub fn factor(self) -> f64 {
+        match self {
+            UiScale::Small => 0.85,
+            UiScale::Medium => 1.0,
+            UiScale::Large => 1.25,
+        }
+    }
+}
+
+impl std::fmt::Display for UiScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UiScale::Small => strings::LABEL_UI_SCALE_SMALL,
+            UiScale::Medium => strings::LABEL_UI_SCALE_MEDIUM,
+            UiScale::Large => strings::LABEL_UI_SCALE_LARGE,
+        })
+    }
+}
+
+/// A pixel count in millions, where zero disables the associated check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Megapixels(pub u32);
+
+impl Megapixels {
+    pub const ALL: [Megapixels; 4] = [Megapixels(0), Megapixels(4), Megapixels(16), Megapixels(64)];
+}
+
+impl Default for Megapixels {
+    fn default() -> Self {
+        Megapixels(16)
+    }
+}
+
+impl std::fmt::Display for Megapixels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str(strings::LABEL_NEVER),
+            megapixels => write!(f, "{megapixels} MP"),
+        }
+    }
+}
+
+/// Guide lines drawn over the box art preview, for checking how art is centered and cropped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreviewGuides {
+    #[default]
+    Off,
+    Thirds,
+    CenterCross,
+}
+
+impl PreviewGuides {
+    pub const ALL: [PreviewGuides; 3] = [
+        PreviewGuides::Off,
+        PreviewGuides::Thirds,
+        PreviewGuides::CenterCross,
+    ];
+
+    /// The option after this one in `ALL`, wrapping around, for cycling with a shortcut.
+    pub fn next(self) -> Self {
+        match self {
+            PreviewGuides::Off => PreviewGuides::Thirds,
+            PreviewGuides::Thirds => PreviewGuides::CenterCross,
+            PreviewGuides::CenterCross => PreviewGuides::Off,
+        }
+    }
+}
+
+impl std::fmt::Display for PreviewGuides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PreviewGuides::Off => strings::LABEL_PREVIEW_GUIDES_OFF,
+            PreviewGuides::Thirds => strings::LABEL_PREVIEW_GUIDES_THIRDS,
+            PreviewGuides::CenterCross => strings::LABEL_PREVIEW_GUIDES_CENTER,
+        })
+    }
+}
+
+/// Largest width and height the box art preview is rendered at, where zero means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviewSize(pub u32);
+
+impl PreviewSize {
+    pub const ALL: [PreviewSize; 4] = [
+        PreviewSize(256),
+        PreviewSize(512),
+        PreviewSize(1024),
+        PreviewSize(0),
+    ];
+}
+
+impl Default for PreviewSize {
+    fn default() -> Self {
+        PreviewSize(512)
+    }
+}
+
+impl std::fmt::Display for PreviewSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str(strings::LABEL_UNLIMITED),
+            max => write!(f, "{max} px"),
+        }
+    }
+}
+
+/// Most gallery thumbnails kept in memory, where zero means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThumbnailCacheLimit(pub usize);
+
+impl ThumbnailCacheLimit {
+    pub const ALL: [ThumbnailCacheLimit; 4] = [
+        ThumbnailCacheLimit(200),
+        ThumbnailCacheLimit(1000),
+        ThumbnailCacheLimit(5000),
+        ThumbnailCacheLimit(0),
+    ];
+}
+
+impl Default for ThumbnailCacheLimit {
+    fn default() -> Self {
+        ThumbnailCacheLimit(1000)
+    }
+}
+
+impl std::fmt::Display for ThumbnailCacheLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str(strings::LABEL_UNLIMITED),
+            max => write!(f, "{max}"),
+        }
+    }
+}
+
+/// Read buffer for box art, in KiB. Larger buffers need fewer round trips on network-mounted cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadBufferSize(pub usize);
+
+impl ReadBufferSize {
+    pub const ALL: [ReadBufferSize; 4] = [
+        ReadBufferSize(8),
+        ReadBufferSize(64),
+        ReadBufferSize(256),
+        ReadBufferSize(1024),
+    ];
+
+    pub fn bytes(self) -> usize {
+        self.0 * 1024
+    }
+}
+
+impl Default for ReadBufferSize {
+    fn default() -> Self {
+        ReadBufferSize(64)
+    }
+}
+
+impl std::fmt::Display for ReadBufferSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} KiB", self.0)
+    }
+}
+
+/// When the `.media` folder of a collection is created if it doesn't exist yet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MediaFolderCreation {
+    #[default]
+    WhenIndexing,
+    OnFirstWrite,
+}
+
+impl MediaFolderCreation {
+    pub const ALL: [MediaFolderCreation; 2] = [
+        MediaFolderCreation::WhenIndexing,
+        MediaFolderCreation::OnFirstWrite,
+    ];
+}
+
+impl std::fmt::Display for MediaFolderCreation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MediaFolderCreation::WhenIndexing => strings::LABEL_MEDIA_FOLDERS_WHEN_INDEXING,
+            MediaFolderCreation::OnFirstWrite => strings::LABEL_MEDIA_FOLDERS_ON_FIRST_WRITE,
+        })
+    }
+}
+
+/// How ROM names are cleaned up for display, sorting, search and art lookups. Files are never renamed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NameNormalization {
+    #[default]
+    Off,
+    /// Drops tags like `(USA)` and `[!]` and collapses the whitespace left behind.
+    StripTags,
+}
+
+impl NameNormalization {
+    pub const ALL: [NameNormalization; 2] = [NameNormalization::Off, NameNormalization::StripTags];
+
+    pub fn apply(self, name: &str) -> String {
+        match self {
+            NameNormalization::Off => name.to_owned(),
+            NameNormalization::StripTags => {
+                let stripped = strip_name_tags(name);
+                // Names that are nothing but tags are kept as they are.
+                if stripped.is_empty() {
+                    name.to_owned()
+                } else {
+                    stripped
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for NameNormalization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            NameNormalization::Off => strings::LABEL_NAMES_AS_ON_DISK,
+            NameNormalization::StripTags => strings::LABEL_NAMES_WITHOUT_TAGS,
+        })
+    }
+}
+
+/// What happens after a ROM is opened with Manage or a click in the gallery, besides showing it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DefaultRomAction {
+    #[default]
+    Preview,
+    ChooseFile,
+    PasteClipboard,
+}
+
+impl DefaultRomAction {
+    pub const ALL: [DefaultRomAction; 3] = [
+        DefaultRomAction::Preview,
+        DefaultRomAction::ChooseFile,
+        DefaultRomAction::PasteClipboard,
+    ];
+}
+
+impl std::fmt::Display for DefaultRomAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DefaultRomAction::Preview => strings::LABEL_ROM_ACTION_PREVIEW,
+            DefaultRomAction::ChooseFile => strings::LABEL_ROM_ACTION_CHOOSE_FILE,
+            DefaultRomAction::PasteClipboard => strings::LABEL_ROM_ACTION_PASTE,
+        })
+    }
+}
+
+/// Kind of `ArtSource` Fetch Art uses.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArtSourceKind {
+    #[default]
+    None,
+    LocalFolder,
+    Http,
+}
+
+impl ArtSourceKind {
+    pub const ALL: [ArtSourceKind; 3] = [
+        ArtSourceKind::None,
+        ArtSourceKind::LocalFolder,
+        ArtSourceKind::Http,
+    ];
+}
+
+impl std::fmt::Display for ArtSourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ArtSourceKind::None => strings::LABEL_ART_SOURCE_NONE,
+            ArtSourceKind::LocalFolder => strings::LABEL_ART_SOURCE_LOCAL_FOLDER,
+            ArtSourceKind::Http => strings::LABEL_ART_SOURCE_HTTP,
+        })
+    }
+}
+
+/// Whether images copied to the clipboard keep their transparency or are flattened onto a background.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClipboardAlpha {
+    #[default]
+    Keep,
+    FlattenWhite,
+    FlattenBlack,
+    FlattenGray,
+}
+
+impl ClipboardAlpha {
+    pub const ALL: [ClipboardAlpha; 4] = [
+        ClipboardAlpha::Keep,
+        ClipboardAlpha::FlattenWhite,
+        ClipboardAlpha::FlattenBlack,
+        ClipboardAlpha::FlattenGray,
+    ];
+
+    pub fn background(self) -> Option<[u8; 3]> {
+        match self {
+            ClipboardAlpha::Keep => None,
+            ClipboardAlpha::FlattenWhite => Some([255, 255, 255]),
+            ClipboardAlpha::FlattenBlack => Some([0, 0, 0]),
+            ClipboardAlpha::FlattenGray => Some([128, 128, 128]),
+        }
+    }
+}
+
+impl std::fmt::Display for ClipboardAlpha {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ClipboardAlpha::Keep => strings::LABEL_CLIPBOARD_ALPHA_KEEP,
+            ClipboardAlpha::FlattenWhite => strings::LABEL_CLIPBOARD_ALPHA_WHITE,
+            ClipboardAlpha::FlattenBlack => strings::LABEL_CLIPBOARD_ALPHA_BLACK,
+            ClipboardAlpha::FlattenGray => strings::LABEL_CLIPBOARD_ALPHA_GRAY,
+        })
+    }
+}
+
+/// How long to wait on a single filesystem call while indexing, in seconds. Zero waits forever.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsTimeout(pub u32);
+
+impl FsTimeout {
+    pub const ALL: [FsTimeout; 4] = [FsTimeout(0), FsTimeout(5), FsTimeout(15), FsTimeout(60)];
+
+    pub fn duration(self) -> Option<Duration> {
+        (self.0 != 0).then(|| Duration::from_secs(self.0.into()))
+    }
+}
+
+impl std::fmt::Display for FsTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => f.write_str(strings::LABEL_NEVER),
+            seconds => write!(f, "{seconds} s"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportResize {
+    #[default]
+    Off,
+    /// Downscale imported art so neither side exceeds this many pixels.
+    Fit(u32),
+}
+
+impl ImportResize {
+    pub const ALL: [ImportResize; 4] = [
+        ImportResize::Off,
+        ImportResize::Fit(256),
+        ImportResize::Fit(512),
+        ImportResize::Fit(1024),
+    ];
+
+    pub fn apply(self, img: DynamicImage, filter: ResizeFilter) -> DynamicImage {
+        match self {
+            ImportResize::Fit(max) if img.width() > max || img.height() > max => {
+                img.resize(max, max, filter.filter_type())
+            }
+            _ => img,
+        }
+    }
+}
+
+impl std::fmt::Display for ImportResize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportResize::Off => f.write_str(strings::LABEL_IMPORT_RESIZE_OFF),
+            ImportResize::Fit(max) => write!(f, "{max} px"),
+        }
+    }
+}
+
+/// Color type box art is saved with. Anything but `Original` converts the image before encoding.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PngColorType {
+    #[default]
+    Original,
+    Rgba8,
+    /// Drops the alpha channel.
+    Rgb8,
+    /// Up to 256 colors in a palette, for the smallest files.
+    Indexed,
+}
+
+impl PngColorType {
+    pub const ALL: [PngColorType; 4] = [
+        PngColorType::Original,
+        PngColorType::Rgba8,
+        PngColorType::Rgb8,
+        PngColorType::Indexed,
+    ];
+
+    pub fn save(self, img: DynamicImage, path: &Path) -> Result<(), String> {
+        let img = match self {
+            PngColorType::Original => img,
+            PngColorType::Rgba8 => DynamicImage::ImageRgba8(img.into_rgba8()),
+            PngColorType::Rgb8 => DynamicImage::ImageRgb8(img.into_rgb8()),
+            PngColorType::Indexed => return save_indexed_png(&img.into_rgba8(), path),
+        };
+        img.save_with_format(path, ImageFormat::Png)
+            .map_err(|e| image_error_detail(&e))
+    }
+}
+
+impl std::fmt::Display for PngColorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PngColorType::Original => strings::LABEL_COLOR_TYPE_ORIGINAL,
+            PngColorType::Rgba8 => strings::LABEL_COLOR_TYPE_RGBA8,
+            PngColorType::Rgb8 => strings::LABEL_COLOR_TYPE_RGB8,
+            PngColorType::Indexed => strings::LABEL_COLOR_TYPE_INDEXED,
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResizeFilter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    #[default]
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    pub const ALL: [ResizeFilter; 4] = [
+        ResizeFilter::Nearest,
+        ResizeFilter::Triangle,
+        ResizeFilter::CatmullRom,
+        ResizeFilter::Lanczos3,
+    ];
+
+    pub fn filter_type(self) -> FilterType {
+        match self {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Triangle => FilterType::Triangle,
+            ResizeFilter::CatmullRom => FilterType::CatmullRom,
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+impl std::fmt::Display for ResizeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ResizeFilter::Nearest => strings::LABEL_FILTER_NEAREST,
+            ResizeFilter::Triangle => strings::LABEL_FILTER_TRIANGLE,
+            ResizeFilter::CatmullRom => strings::LABEL_FILTER_CATMULL_ROM,
+            ResizeFilter::Lanczos3 => strings::LABEL_FILTER_LANCZOS3,
+        })
+    }
+}
+
+impl std::fmt::Display for SizeUnits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SizeUnits::Binary => strings::LABEL_SIZE_UNITS_BINARY,
+            SizeUnits::Decimal => strings::LABEL_SIZE_UNITS_DECIMAL,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Collection {
+    pub name: String,
+    pub rom_indices: Vec<usize>,
+    pub manifest: Option<CollectionManifest>,
+    /// The folder holds a `.nomedia` marker, so no media folder is created and no art expected.
+    pub no_media: bool,
+    /// Where the collection's art was indexed from. Systems split out of one folder by extension
+    /// share that folder's, which their own names wouldn't lead to.
+    #[serde(serialize_with = "serialize_path_lossy")]
+    pub media_folder: PathBuf,
+}
+
+/// Art conventions of one system, read from its media folder and taking precedence over the
+/// global settings, so a card can mix systems that name or size their art differently.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectionManifest {
+    /// Replaces `boxart_template` for this collection.
+    pub boxart_template: Option<String>,
+    /// Longest side imported art is downscaled to, replacing `import_resize`.
+    pub max_art_size: Option<u32>,
+}
+
+impl CollectionManifest {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        match &manifest.boxart_template {
+            Some(template) if !PersistentConfig::is_valid_boxart_template(template) => {
+                Err(strings::ERROR_INVALID_BOXART_TEMPLATE.into())
+            }
+            _ if manifest.max_art_size == Some(0) => {
+                Err(strings::ERROR_INVALID_MAX_ART_SIZE.into())
+            }
+            _ => Ok(manifest),
+        }
+    }
+
+    /// `config` with this manifest's settings in place of the global ones.
+    pub fn apply(&self, config: &PersistentConfig) -> PersistentConfig {
+        let mut config = config.clone();
+        if let Some(template) = &self.boxart_template {
+            config.boxart_template = template.clone();
+        }
+        if let Some(max_art_size) = self.max_art_size {
+            config.import_resize = ImportResize::Fit(max_art_size);
+        }
+        config
+    }
+}
+
+/// Names and art paths an EmulationStation-style frontend keeps for a collection's ROMs.
+#[derive(Debug, Default, Clone)]
+pub struct Gamelist {
+    /// Keyed by ROM file name, for the ROMs directly in the collection folder.
+    entries: HashMap<OsString, GamelistEntry>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GamelistEntry {
+    pub name: Option<String>,
+    /// Box art, resolved against the folder the gamelist is in.
+    pub image: Option<PathBuf>,
+}
+
+impl Gamelist {
+    /// Parses a `gamelist.xml` from `collection_path`, which its relative paths start from.
+    pub fn parse(content: &str, collection_path: &Path) -> Result<Self, String> {
+        let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+        let mut entries = HashMap::new();
+        for game in document
+            .root_element()
+            .children()
+            .filter(|x| x.has_tag_name("game"))
+        {
+            let field = |tag: &str| {
+                game.children()
+                    .find(|x| x.has_tag_name(tag))
+                    .and_then(|x| x.text())
+                    .map(str::trim)
+                    .filter(|x| !x.is_empty())
+            };
+            // ROMs are only indexed from the top of the collection, so an entry in a subfolder
+            // would otherwise take over the one for a top-level ROM of the same file name.
+            let Some(rom_path) = field("path").map(|x| resolve_gamelist_path(x, collection_path))
+            else {
+                continue;
+            };
+            let Some(file_name) = rom_path
+                .file_name()
+                .filter(|_| rom_path.parent() == Some(collection_path))
+            else {
+                continue;
+            };
+            entries.insert(
+                file_name.to_os_string(),
+                GamelistEntry {
+                    name: field("name").map(str::to_owned),
+                    image: field("image")
+                        .or_else(|| field("thumbnail"))
+                        .map(|x| resolve_gamelist_path(x, collection_path)),
+                },
+            );
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn get(&self, rom_file_name: &OsStr) -> Option<&GamelistEntry> {
+        self.entries.get(rom_file_name)
+    }
+}
+
+/// Gamelists write paths relative to their folder as `./...`, and sometimes to the home folder as `~/...`.
+fn resolve_gamelist_path(path: &str, collection_path: &Path) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => directories::BaseDirs::new()
+            .map_or_else(|| collection_path.join(rest), |x| x.home_dir().join(rest)),
+        None => collection_path.join(path.strip_prefix("./").unwrap_or(path)),
+    }
+}
+
+impl Collection {
+    pub fn missing_art_count(&self, roms: &[Rom]) -> usize {
+        if self.no_media {
+            return 0;
+        }
+        self.rom_indices
+            .iter()
+            .filter(|x| roms.get(**x).is_some_and(|rom| rom.boxart_size == 0))
+            .count()
+    }
+
+    /// One line describing the collection's progress, for pasting into notes or a spreadsheet.
+    pub fn summary(&self, roms: &[Rom], size_units: SizeUnits) -> String {
+        let art_size = self
+            .rom_indices
+            .iter()
+            .filter_map(|x| roms.get(*x))
+            .map(|rom| rom.boxart_size)
+            .sum();
+
+        format!(
+            "{}: {} {}, {} {}, {} {}",
+            self.name,
+            self.rom_indices.len(),
+            strings::LABEL_ROMS,
+            self.missing_art_count(roms),
+            strings::LABEL_MISSING_BOX_ART,
+            ByteSizeFormatter::format_auto(art_size, size_units.system()),
+            strings::LABEL_OF_BOX_ART
+        )
+    }
+}
+
+/// Outcome of writing box art for each ROM index in a batch operation.
+pub type BatchWriteResults = Vec<(usize, Result<(ArtChange, u64), String>)>;
+/// New size of each ROM's art after undoing or redoing a change, or why it couldn't be restored.
+pub type RestoreResults = Vec<(usize, Result<u64, String>)>;
+/// New box art path of each ROM with its art's size there, or why its art couldn't be moved.
+pub type BoxartRenameResults = Vec<(usize, Result<(PathBuf, u64), String>)>;
+/// New art path of each ROM whose art moved back or forth by undo or redo, if its path changed,
+/// with the art's new size, or why it couldn't be moved.
+pub type RenameRestoreResults = Vec<(usize, Option<PathBuf>, Result<u64, String>)>;
+/// Orphaned file and the ROM it was renamed for, with the art's size and modification time.
+pub type OrphanRenameResults = Vec<(PathBuf, usize, Result<(u64, Option<SystemTime>), String>)>;
+
+/// Brightness and contrast changes applied to art before it's saved. Zero leaves the image untouched.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtAdjustment {
+    pub brightness: i32,
+    pub contrast: i32,
+}
+
+impl ArtAdjustment {
+    pub const RANGE: std::ops::RangeInclusive<i32> = -100..=100;
+
+    pub fn is_neutral(self) -> bool {
+        self == Self::default()
+    }
+
+    pub fn apply(self, img: &DynamicImage) -> DynamicImage {
+        let mut adjusted = if self.brightness != 0 {
+            img.brighten(self.brightness)
+        } else {
+            img.clone()
+        };
+        if self.contrast != 0 {
+            adjusted = adjusted.adjust_contrast(self.contrast as f32);
+        }
+        adjusted
+    }
+}
+
+/// Box art replaced or deleted this session, with copies of both versions so it can be undone and redone.
+#[derive(Debug, Clone)]
+pub struct ArtChange {
+    pub rom_index: usize,
+    pub boxart_path: PathBuf,
+    /// Copy of the art before the change, or `None` if the ROM had no art.
+    pub before: Option<PathBuf>,
+    pub after: Option<PathBuf>,
+}
+
+impl ArtChange {
+    /// Removes the copies once the change can no longer be undone or redone.
+    pub fn discard(self) {
+        for backup in [self.before, self.after].into_iter().flatten() {
+            if let Err(e) = std::fs::remove_file(&backup) {
+                log::warn!("Failed to remove undo copy '{}': {e}", backup.display());
+            }
+        }
+    }
+}
+
+/// Art moved to another file name this session, so it can be moved back and forth.
+#[derive(Debug, Clone)]
+pub struct ArtRename {
+    pub rom_index: usize,
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// The ROM's art path follows the file, as with a template migration. Otherwise the ROM's art
+    /// is at `to`, as with orphaned art given to it, and it has none while the file is at `from`.
+    pub moves_boxart_path: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct State {
+    pub roms_folder: PathBuf,
+    pub index: Index,
+    pub errors: Vec<String>,
+    /// Entries of `errors` that indexing found, which a reindex replaces instead of adding to.
+    pub index_errors: Vec<String>,
+    /// Folders that were scanned but contained no ROMs.
+    pub skipped_collections: Vec<PathBuf>,
+    /// Number of entries in `errors` that came from indexing each collection, keyed by name.
+    pub collection_errors: HashMap<String, usize>,
+    /// The Roms folder held ROMs directly instead of collection folders and was indexed as one collection.
+    pub single_collection: bool,
+    /// Coverage at the end of the previous session, if one was recorded.
+    pub previous_coverage: Option<CoverageSnapshot>,
+    /// ROMs whose box art was written or deleted this session, most recent last.
+    pub recently_modified: Vec<usize>,
+    /// Number of changes made to ROMs' art and notes from the app. A scan copies it when it starts,
+    /// so merging it can tell which changes happened while it ran.
+    pub write_count: u64,
+    /// The `write_count` each ROM's art or note was last changed at.
+    pub rom_writes: HashMap<usize, u64>,
+    pub config: PersistentConfig,
+}
+
+impl State {
+    /// A state for `roms_folder` with nothing indexed yet. Call `index_roms` to fill it.
+    pub fn new(roms_folder: PathBuf, config: PersistentConfig) -> Self {
+        Self {
+            roms_folder,
+            index: Index::default(),
+            errors: Vec::new(),
+            index_errors: Vec::new(),
+            skipped_collections: Vec::new(),
+            collection_errors: HashMap::new(),
+            single_collection: false,
+            previous_coverage: None,
+            recently_modified: Vec::new(),
+            write_count: 0,
+            rom_writes: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Records that the app changed a ROM's art or note, which a scan that's running can't see.
+    pub fn mark_rom_written(&mut self, rom_index: usize) {
+        self.write_count += 1;
+        self.rom_writes.insert(rom_index, self.write_count);
+    }
+
+    /// Slots of `index.roms` that no collection lists, lowest last so they're taken in order.
+    fn unlisted_slots(&self) -> Vec<usize> {
+        let listed: HashSet<usize> = self.index.listed_roms().map(|(x, _)| x).collect();
+        (0..self.index.roms.len())
+            .rev()
+            .filter(|x| !listed.contains(x))
+            .collect()
+    }
+
+    /// Art isn't expected in the collection, either by setting or by a `.nomedia` marker.
+    pub fn is_art_exempt(&self, collection: &Collection) -> bool {
+        collection.no_media
+            || self
+                .config
+                .art_exempt_collections
+                .contains(&collection.name)
+    }
+
+    /// Names of collections that differ only in case, which look like duplicates in the list.
+    pub fn case_duplicate_groups(&self) -> Vec<Vec<&str>> {
+        let mut groups: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for collection in &self.index.collections {
+            groups
+                .entry(collection.name.to_lowercase())
+                .or_default()
+                .push(&collection.name);
+        }
+        groups.into_values().filter(|x| x.len() > 1).collect()
+    }
+
+    /// Folds collections whose names differ only in case into the first one listed.
+    pub fn merge_case_duplicates(&mut self) {
+        let mut merged: Vec<Collection> = Vec::new();
+        for collection in std::mem::take(&mut self.index.collections) {
+            let name = collection.name.to_lowercase();
+            let Some(existing) = merged.iter_mut().find(|x| x.name.to_lowercase() == name) else {
+                merged.push(collection);
+                continue;
+            };
+            existing.rom_indices.extend(collection.rom_indices);
+            if let Some(count) = self.collection_errors.remove(&collection.name) {
+                *self
+                    .collection_errors
+                    .entry(existing.name.clone())
+                    .or_default() += count;
+            }
+        }
+        self.index.collections = merged;
+    }
+
+    /// ROMs without art anywhere in the library that share `rom_index`'s normalized name.
+    pub fn matching_roms_missing_art(&self, rom_index: usize) -> Vec<usize> {
+        let Some(rom) = self.index.roms.get(rom_index) else {
+            return Vec::new();
+        };
+        let name = normalized_rom_name(&rom.name);
+        self.index
+            .listed_roms()
+            .filter(|(index, other)| {
+                *index != rom_index
+                    && other.boxart_size == 0
+                    && normalized_rom_name(&other.name) == name
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Puts a fresh scan of one collection in place of its ROMs and returns their indices. ROMs that
+    /// are still there keep their index, so selections and undo history stay valid. ROMs that are
+    /// gone stay in `index.roms` unlisted until a later merge gives their slot to a new ROM.
+    pub fn merge_rescanned_collection(
+        &mut self,
+        collection_name: &str,
+        rescanned: State,
+    ) -> Vec<usize> {
+        let Some(position) = self
+            .index
+            .collections
+            .iter()
+            .position(|x| x.name == collection_name)
+        else {
+            return Vec::new();
+        };
+        let scanned_collection = rescanned
+            .index
+            .collections
+            .iter()
+            .find(|x| x.name == collection_name);
+        let scanned = scanned_collection.map_or(&[][..], |x| &x.rom_indices);
+        let mut free_slots = self.unlisted_slots();
+        let rom_indices =
+            self.merge_collection_roms(collection_name, scanned, &rescanned, &mut free_slots);
+        self.index.collections[position].rom_indices = rom_indices.clone();
+        self.index.collections[position].manifest =
+            scanned_collection.and_then(|x| x.manifest.clone());
+        self.index.collections[position].no_media = scanned_collection.is_some_and(|x| x.no_media);
+        if let Some(scanned_collection) = scanned_collection {
+            self.index.collections[position]
+                .media_folder
+                .clone_from(&scanned_collection.media_folder);
+        }
+
+        if rescanned.errors.is_empty() {
+            self.collection_errors.remove(collection_name);
+        } else {
+            self.collection_errors
+                .insert(collection_name.to_owned(), rescanned.errors.len());
+        }
+        self.index_errors.extend(rescanned.errors.iter().cloned());
+        self.errors.extend(rescanned.errors);
+        rom_indices
+    }
+
+    /// Takes in a reindex of the whole Roms folder done on another `State`. ROMs are matched by
+    /// collection and file stem and keep their index, so selections and in-flight work still
+    /// point at the same ROM. ROMs that are gone stay in `index.roms` unlisted until a later merge
+    /// gives their slot to a new ROM.
+    pub fn merge_reindexed(&mut self, reindexed: State) {
+        let mut collections = Vec::with_capacity(reindexed.index.collections.len());
+        let mut free_slots = self.unlisted_slots();
+        for scanned in &reindexed.index.collections {
+            let rom_indices = self.merge_collection_roms(
+                &scanned.name,
+                &scanned.rom_indices,
+                &reindexed,
+                &mut free_slots,
+            );
+            collections.push(Collection {
+                rom_indices,
+                ..scanned.clone()
+            });
+        }
+        self.index.collections = collections;
+        self.skipped_collections = reindexed.skipped_collections;
+        self.collection_errors = reindexed.collection_errors;
+        self.single_collection = reindexed.single_collection;
+        for error in std::mem::take(&mut self.index_errors) {
+            if let Some(position) = self.errors.iter().position(|x| *x == error) {
+                self.errors.remove(position);
+            }
+        }
+        // Everything the reindex recorded came from indexing, as it started out empty.
+        self.errors.extend(reindexed.errors.iter().cloned());
+        self.index_errors = reindexed.errors;
+    }
+
+    /// Stores the `scanned` ROMs from `scan`, reusing the slots of the ROMs with the same stem
+    /// that `collection_name` listed before, and returns their indices. New ROMs take `free_slots`
+    /// first. Only slots freed by an earlier merge are passed in, as selections still point at the
+    /// ROMs this one drops until they're reconciled.
+    fn merge_collection_roms(
+        &mut self,
+        collection_name: &str,
+        scanned: &[usize],
+        scan: &State,
+        free_slots: &mut Vec<usize>,
+    ) -> Vec<usize> {
+        let existing: HashMap<OsString, usize> = self
+            .index
+            .collections
+            .iter()
+            .find(|x| x.name == collection_name)
+            .map_or(&[][..], |x| &x.rom_indices)
+            .iter()
+            .filter_map(|x| Some((self.index.roms.get(*x)?.stem.clone(), *x)))
+            .collect();
+
+        let mut rom_indices = Vec::with_capacity(scanned.len());
+        for rom in scanned.iter().filter_map(|x| scan.index.roms.get(*x)) {
+            match existing.get(&rom.stem) {
+                Some(index) => {
+                    let current = std::mem::replace(&mut self.index.roms[*index], rom.clone());
+                    // Changed from the app after the scan started, so what it read is out of date.
+                    if self
+                        .rom_writes
+                        .get(index)
+                        .is_some_and(|x| *x > scan.write_count)
+                    {
+                        let merged = &mut self.index.roms[*index];
+                        merged.boxart_size = current.boxart_size;
+                        merged.boxart_modified = current.boxart_modified;
+                        merged.boxart_has_alpha = current.boxart_has_alpha;
+                        merged.boxart_frames = current.boxart_frames;
+                        merged.boxart_dimensions = current.boxart_dimensions;
+                        merged.note = current.note;
+                    }
+                    rom_indices.push(*index);
+                }
+                None => match free_slots.pop() {
+                    Some(slot) => {
+                        self.index.roms[slot] = rom.clone();
+                        self.rom_writes.remove(&slot);
+                        self.recently_modified.retain(|x| *x != slot);
+                        rom_indices.push(slot);
+                    }
+                    None => {
+                        self.index.roms.push(rom.clone());
+                        rom_indices.push(self.index.roms.len() - 1);
+                    }
+                },
+            }
+        }
+        rom_indices
+    }
+
+    pub fn collection_of(&self, rom_index: usize) -> Option<&Collection> {
+        self.index
+            .collections
+            .iter()
+            .find(|x| x.rom_indices.contains(&rom_index))
+    }
+
+    pub fn collection_folder(&self, collection_name: &OsStr) -> PathBuf {
+        if self.single_collection {
+            self.roms_folder.clone()
+        } else {
+            self.roms_folder.join(collection_name)
+        }
+    }
+
+    /// The collection an error is about, judging by the folder paths it mentions.
+    pub fn collection_of_error(&self, error: &str) -> Option<&str> {
+        // Followed by a separator or the closing quote, so "GB" doesn't also match "GBA".
+        let mentions = |folder: PathBuf| {
+            let folder = folder.to_string_lossy();
+            error.contains(&format!("{folder}{}", std::path::MAIN_SEPARATOR))
+                || error.contains(&format!("{folder}'"))
+        };
+        self.index
+            .collections
+            .iter()
+            .find(|x| {
+                mentions(self.collection_folder(x.name.as_ref()))
+                    || mentions(x.media_folder.clone())
+            })
+            .map(|x| x.name.as_str())
+    }
+
+    /// Where a ROM's box art belongs under the current naming template.
+    pub fn templated_boxart_path(&self, rom_index: usize, rom: &Rom) -> PathBuf {
+        rom.boxart_path
+            .with_file_name(self.rom_config(rom_index).boxart_file_name(&rom.stem))
+    }
+
+    /// How many listed ROMs have art named differently from what their template would produce
+    /// now, working out each collection's settings once rather than once per ROM.
+    pub fn stale_boxart_path_count(&self) -> usize {
+        self.index
+            .collections
+            .iter()
+            .map(|collection| {
+                let config = match &collection.manifest {
+                    Some(manifest) => Cow::Owned(manifest.apply(&self.config)),
+                    None => Cow::Borrowed(&self.config),
+                };
+                collection
+                    .rom_indices
+                    .iter()
+                    .filter_map(|x| self.index.roms.get(*x))
+                    .filter(|rom| {
+                        rom.boxart_path.file_name()
+                            != Some(config.boxart_file_name(&rom.stem).as_os_str())
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Settings that apply to a ROM's art, with its collection's manifest taking precedence.
+    pub fn rom_config(&self, rom_index: usize) -> Cow<'_, PersistentConfig> {
+        match self
+            .collection_of(rom_index)
+            .and_then(|x| x.manifest.as_ref())
+        {
+            Some(manifest) => Cow::Owned(manifest.apply(&self.config)),
+            None => Cow::Borrowed(&self.config),
+        }
+    }
+
+    pub fn media_folder(&self, collection_name: &OsStr) -> PathBuf {
+        match &self.config.art_root {
+            Some(art_root) => art_root.join(collection_name),
+            None => self.collection_folder(collection_name).join(".media"),
+        }
+    }
+
+    /// Returns why `new_name` can't be used to rename `old_name`, if it can't.
+    pub fn collection_rename_error(&self, old_name: &str, new_name: &str) -> Option<&'static str> {
+        if new_name.trim().is_empty()
+            || new_name != new_name.trim()
+            || new_name.starts_with('.')
+            || new_name.contains(INVALID_NAME_CHARACTERS)
+        {
+            Some(strings::ERROR_INVALID_COLLECTION_NAME)
+        } else if new_name != old_name
+            && (self.index.collections.iter().any(|x| x.name == new_name)
+                || self.roms_folder.join(new_name).exists())
+        {
+            Some(strings::ERROR_COLLECTION_EXISTS)
+        } else {
+            None
+        }
+    }
+
+    /// Indexes all collections, reporting filesystem calls that are taking unusually long to `on_slow`.
+    pub fn index_roms(
+        &mut self,
+        on_slow: &mut dyn FnMut(String),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        log::info!("Indexing '{}'", self.roms_folder.display());
+        if let Ok(metadata) = fs_call(
+            self.config.fs_timeout,
+            &self.roms_folder,
+            |x| std::fs::metadata(x),
+            on_slow,
+        ) && !metadata.is_dir()
+        {
+            return Err(format!(
+                "{}{}'{}",
+                strings::ERROR_PREFIX_ROMS_PATH_NOT_DIR,
+                self.roms_folder.display(),
+                strings::ERROR_SUFFIX_ROMS_PATH_NOT_DIR
+            )
+            .into());
+        }
+
+        let read_dir = fs_call(self.config.fs_timeout, &self.roms_folder, list_dir, on_slow)
+            .map_err(|e| {
+                format!(
+                    "{}{}': {}",
+                    strings::ERROR_PREFIX_DIR_READ,
+                    self.roms_folder.display(),
+                    e
+                )
+            })?;
+
+        let errors_before = self.errors.len();
+        let mut has_loose_files = false;
+        for entry_result in read_dir {
+            if let Ok(entry) = entry_result {
+                let entry_path = entry.path();
+                match entry.file_type() {
+                    Ok(file_type) => {
+                        if file_type.is_dir()
+                            && !entry.path().ends_with(".media")
+                            && self.config.art_root.as_ref() != Some(&entry_path)
+                            && !self
+                                .config
+                                .is_excluded_collection(&entry.file_name().to_string_lossy())
+                        {
+                            self.index_collection(entry_path, entry.file_name(), on_slow);
+                        } else if file_type.is_file()
+                            && !entry.file_name().to_string_lossy().starts_with('.')
+                        {
+                            has_loose_files = true;
+                        }
+                    }
+                    Err(e) => {
+                        self.errors.push(format!(
+                            "{}{}': {}",
+                            strings::ERROR_PREFIX_DIR_TYPE,
+                            entry.path().display(),
+                            e
+                        ));
+                    }
+                }
+            } else if let Err(e) = entry_result {
+                self.errors
+                    .push(format!("{}{}", strings::ERROR_PREFIX_DIR_ENTRY, e));
+            }
+        }
+
+        // Pointed at a single system's folder, so treat the folder itself as the only collection.
+        if has_loose_files
+            && self
+                .index
+                .collections
+                .iter()
+                .all(|x| x.rom_indices.is_empty())
+        {
+            self.index = Index::default();
+            self.collection_errors.clear();
+            self.single_collection = true;
+            let collection_name = self
+                .roms_folder
+                .file_name()
+                .map_or_else(|| self.roms_folder.as_os_str().to_owned(), OsStr::to_owned);
+            self.index_collection(self.roms_folder.clone(), collection_name, on_slow);
+        }
+
+        let (collections, skipped) = std::mem::take(&mut self.index.collections)
+            .into_iter()
+            .partition(|x| {
+                !x.rom_indices.is_empty() || self.collection_errors.contains_key(&x.name)
+            });
+        self.index.collections = collections;
+        self.skipped_collections = skipped
+            .into_iter()
+            .map(|x: Collection| self.roms_folder.join(x.name))
+            .collect();
+
+        for group in self.case_duplicate_groups() {
+            log::warn!("Collections differ only in case: {}", group.join(", "));
+        }
+        if self.config.merge_case_duplicates {
+            self.merge_case_duplicates();
+        }
+
+        self.index_errors = self.errors[errors_before..].to_vec();
+        log::info!(
+            "Indexed {} ROMs in {} collections with {} errors",
+            self.index.roms.len(),
+            self.index.collections.len(),
+            self.errors.len()
+        );
+        Ok(())
+    }
+
+    /// Indexes one collection, keeping track of the errors it produced.
+    pub fn index_collection(
+        &mut self,
+        collection_path: PathBuf,
+        collection_name_os: OsString,
+        on_slow: &mut dyn FnMut(String),
+    ) {
+        let collection_name = collection_name_os.to_string_lossy().to_string();
+        let errors_before = self.errors.len();
+        if let Err(e) = self.index_collection_folder(&collection_path, &collection_name_os, on_slow)
+        {
+            self.errors.push(format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_INDEX_COLLECTION,
+                collection_path.display(),
+                e
+            ));
+            // Keep it listed so the failure shows up next to the collection.
+            self.index.collections.push(Collection {
+                name: collection_name.clone(),
+                rom_indices: Vec::new(),
+                manifest: None,
+                no_media: false,
+                media_folder: self.media_folder(&collection_name_os),
+            });
+        }
+
+        let error_count = self.errors.len() - errors_before;
+        for error in &self.errors[errors_before..] {
+            log::warn!("{error}");
+        }
+        log::debug!(
+            "Indexed collection '{}' with {} errors",
+            collection_path.display(),
+            error_count
+        );
+        if error_count > 0 {
+            self.collection_errors.insert(collection_name, error_count);
+        }
+    }
+
+    pub fn index_collection_folder(
+        &mut self,
+        collection_path: &Path,
+        collection_name_os: &OsStr,
+        on_slow: &mut dyn FnMut(String),
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let timeout = self.config.fs_timeout;
+        let collection_name = collection_name_os.to_string_lossy();
+
+        let mut collection = Collection {
+            name: collection_name.to_string(),
+            rom_indices: Vec::new(),
+            manifest: None,
+            no_media: fs_call(
+                timeout,
+                &collection_path.join(NO_MEDIA_MARKER_FILE_NAME),
+                |x| std::fs::metadata(x),
+                on_slow,
+            )
+            .is_ok(),
+            media_folder: self.media_folder(collection_name_os),
+        };
+        let group_by_extension = self.single_collection && self.config.group_by_extension;
+        let mut extension_groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+        let read_dir = fs_call(timeout, collection_path, list_dir, on_slow).map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_READ_COLLECTION,
+                collection_path.display(),
+                e
+            )
+        })?;
+
+        let media_folder = collection.media_folder.clone();
+        let mut media_folder_exists = true;
+        match fs_call(timeout, &media_folder, |x| std::fs::metadata(x), on_slow) {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => {
+                return Err(format!(
+                    "{}{}'{}",
+                    strings::ERROR_PREFIX_MEDIA_NOT_DIR,
+                    media_folder.display(),
+                    strings::ERROR_SUFFIX_MEDIA_NOT_DIR
+                )
+                .into());
+            }
+            // Left for the first write to create, so browsing doesn't modify the library.
+            Err(_)
+                if self.config.media_folder_creation == MediaFolderCreation::OnFirstWrite
+                    || self.config.is_read_only()
+                    || collection.no_media =>
+            {
+                media_folder_exists = false;
+            }
+            Err(_) => {
+                fs_call(
+                    timeout,
+                    &media_folder,
+                    |x| std::fs::create_dir_all(x),
+                    on_slow,
+                )
+                .map_err(|e| {
+                    format!(
+                        "{}{}': {}",
+                        strings::ERROR_PREFIX_MEDIA_DIR,
+                        media_folder.display(),
+                        write_error_detail(&e)
+                    )
+                })?;
+            }
+        }
+
+        if media_folder_exists {
+            let manifest_path = media_folder.join(COLLECTION_MANIFEST_FILE_NAME);
+            let manifest = match fs_call(
+                timeout,
+                &manifest_path,
+                |x| std::fs::read_to_string(x),
+                on_slow,
+            ) {
+                Ok(content) => CollectionManifest::parse(&content).map(Some),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.to_string()),
+            };
+            match manifest {
+                Ok(manifest) => collection.manifest = manifest,
+                Err(e) => self.errors.push(format!(
+                    "{}{}': {}",
+                    strings::ERROR_PREFIX_READ_MANIFEST,
+                    manifest_path.display(),
+                    e
+                )),
+            }
+        }
+        let config = match &collection.manifest {
+            Some(manifest) => Cow::Owned(manifest.apply(&self.config)),
+            None => Cow::Borrowed(&self.config),
+        };
+
+        let gamelist = if config.read_gamelists {
+            let gamelist_path = collection_path.join(GAMELIST_FILE_NAME);
+            let gamelist = match fs_call(
+                timeout,
+                &gamelist_path,
+                |x| std::fs::read_to_string(x),
+                on_slow,
+            ) {
+                Ok(content) => Gamelist::parse(&content, collection_path).map(Some),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.to_string()),
+            };
+            gamelist.unwrap_or_else(|e| {
+                self.errors.push(format!(
+                    "{}{}': {}",
+                    strings::ERROR_PREFIX_READ_GAMELIST,
+                    gamelist_path.display(),
+                    e
+                ));
+                None
+            })
+        } else {
+            None
+        };
+
+        // Listed once up front so ROMs without a note don't cost a filesystem call each.
+        let note_stems: HashSet<OsString> = if media_folder_exists {
+            fs_call(timeout, &media_folder, list_dir, on_slow)
+                .map(|entries| {
+                    entries
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|x| {
+                            let file_name = x.file_name();
+                            let stem = file_name.to_str()?.strip_suffix(NOTE_FILE_SUFFIX)?;
+                            Some(OsString::from(stem))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            HashSet::new()
+        };
+
+        for entry in read_dir.into_iter().flatten() {
+            if !entry.file_type()?.is_file()
+                || entry.file_name() == NO_MEDIA_MARKER_FILE_NAME
+                || entry.file_name() == GAMELIST_FILE_NAME
+            {
+                continue;
+            }
+
+            let entry_path = entry.path();
+            let file_stem = entry_path.file_stem().ok_or(format!(
+                "{}{:#?}",
+                strings::ERROR_PREFIX_FILE_STEM,
+                entry
+            ))?;
+
+            if file_stem.to_str().is_none() {
+                self.errors.push(format!(
+                    "{}{}'",
+                    strings::ERROR_PREFIX_LOSSY_FILE_NAME,
+                    entry_path.display()
+                ));
+            }
+
+            let listed = gamelist
+                .as_ref()
+                .and_then(|x| x.get(&entry.file_name()))
+                .cloned()
+                .unwrap_or_default();
+            // Built from the raw stem so non-UTF-8 names still map to the right file.
+            let boxart_path = listed.image.unwrap_or_else(|| {
+                let mut boxart_path = media_folder.clone();
+                boxart_path.push(config.boxart_file_name(file_stem));
+                boxart_path
+            });
+            // Art a gamelist points at can live outside the media folder, where notes weren't listed.
+            let in_media_folder = boxart_path.parent() == Some(media_folder.as_path());
+
+            let display_name = listed.name.unwrap_or_else(|| {
+                config
+                    .name_normalization
+                    .apply(&file_stem.to_string_lossy())
+            });
+            let mut rom = Rom::new(file_stem, display_name, boxart_path.clone());
+
+            if note_stems.contains(file_stem) || !in_media_folder {
+                let note_path = rom.note_path();
+                match fs_call(timeout, &note_path, |x| std::fs::read_to_string(x), on_slow) {
+                    Ok(note) => rom.note = note,
+                    Err(e) if !in_media_folder && e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        self.errors.push(format!(
+                            "{}{}': {}",
+                            strings::ERROR_PREFIX_READ_NOTE,
+                            note_path.display(),
+                            e
+                        ));
+                    }
+                }
+            }
+
+            if media_folder_exists || !in_media_folder {
+                match fs_call(timeout, &boxart_path, |x| std::fs::metadata(x), on_slow) {
+                    Ok(metadata) => {
+                        rom.boxart_size = metadata.len();
+                        rom.boxart_modified = metadata.modified().ok();
+                        if config.read_art_dimensions && rom.boxart_size != 0 {
+                            match fs_call(timeout, &boxart_path, read_image_dimensions, on_slow) {
+                                Ok(dimensions) => rom.boxart_dimensions = Some(dimensions),
+                                Err(e) => self.errors.push(format!(
+                                    "{}{}': {}",
+                                    strings::ERROR_PREFIX_READ_DIMENSIONS,
+                                    boxart_path.display(),
+                                    e
+                                )),
+                            }
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        self.errors.push(format!(
+                            "{}{}: {}'",
+                            strings::ERROR_PREFIX_GET_METADATA,
+                            boxart_path.display(),
+                            e
+                        ));
+                    }
+                }
+            }
+
+            self.index.roms.push(rom);
+            let rom_index = self.index.roms.len() - 1;
+            match entry_path
+                .extension()
+                .and_then(|x| config.extension_system(x))
+            {
+                Some(system) if group_by_extension => extension_groups
+                    .entry(system.to_owned())
+                    .or_default()
+                    .push(rom_index),
+                _ => collection.rom_indices.push(rom_index),
+            }
+        }
+
+        // Systems split out of one folder share its media folder, and so its manifest.
+        let manifest = collection.manifest.clone();
+        let no_media = collection.no_media;
+        // ROMs with unknown extensions stay in the folder's own collection.
+        if !group_by_extension || !collection.rom_indices.is_empty() {
+            self.index.collections.push(collection);
+        }
+        self.index
+            .collections
+            .extend(
+                extension_groups
+                    .into_iter()
+                    .map(|(name, rom_indices)| Collection {
+                        name,
+                        rom_indices,
+                        manifest: manifest.clone(),
+                        no_media,
+                        media_folder: media_folder.clone(),
+                    }),
+            );
+
+        Ok(())
+    }
+}
+
+/// Finds files in `media_folders` that aren't one of `boxart_paths`, the files indexed ROMs own.
+pub fn find_orphaned_art(
+    media_folders: &BTreeSet<PathBuf>,
+    boxart_paths: &HashSet<PathBuf>,
+) -> Vec<(PathBuf, u64)> {
+    let mut orphans: Vec<(PathBuf, u64)> = media_folders
+        .iter()
+        .filter_map(|folder| std::fs::read_dir(folder).ok())
+        .flat_map(|read_dir| read_dir.flatten())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let path = entry.path();
+            (metadata.is_file()
+                && !boxart_paths.contains(&path)
+                && entry.file_name() != COLLECTION_MANIFEST_FILE_NAME)
+                .then_some((path, metadata.len()))
+        })
+        .collect();
+    orphans.sort();
+
+    orphans
+}
+
+/// Pairs orphaned files with ROMs that have no art when their file names only differ in case,
+/// punctuation, or tags like `(USA)` and `[!]`, which is what tidying up ROM names usually
+/// changes. Orphans and ROMs that could be paired more than one way are left out.
+pub fn suggest_art_renames(orphans: &[(PathBuf, u64)], index: &Index) -> Vec<(PathBuf, usize)> {
+    let match_key = |path: &Path| {
+        Some((
+            path.parent()?.to_path_buf(),
+            normalized_rom_name(&path.file_name()?.to_string_lossy()),
+        ))
+    };
+
+    let mut missing: HashMap<(PathBuf, String), Vec<usize>> = HashMap::new();
+    for (rom_index, rom) in index.listed_roms().filter(|(_, x)| x.boxart_size == 0) {
+        if let Some(key) = match_key(&rom.boxart_path) {
+            missing.entry(key).or_default().push(rom_index);
+        }
+    }
+
+    let mut candidates: HashMap<usize, Vec<&Path>> = HashMap::new();
+    for (path, _) in orphans {
+        if let Some(rom_indices) = match_key(path).and_then(|x| missing.get(&x))
+            && let [rom_index] = rom_indices[..]
+        {
+            candidates.entry(rom_index).or_default().push(path);
+        }
+    }
+
+    let mut renames: Vec<(PathBuf, usize)> = candidates
+        .into_iter()
+        .filter_map(|(rom_index, paths)| match paths[..] {
+            [path] => Some((path.to_path_buf(), rom_index)),
+            _ => None,
+        })
+        .collect();
+    renames.sort();
+    renames
+}
+
+/// Saves a ROM's note, removing the sidecar file once the note is cleared.
+pub fn write_note(note_path: &Path, note: &str) -> Result<(), String> {
+    let result = if note.trim().is_empty() {
+        match std::fs::remove_file(note_path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        }
+    } else {
+        note_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(note_path, note))
+    };
+
+    result.map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_SAVE_NOTE,
+            note_path.display(),
+            write_error_detail(&e)
+        )
+    })
+}
+
+fn list_dir(path: &Path) -> io::Result<Vec<io::Result<DirEntry>>> {
+    Ok(std::fs::read_dir(path)?.collect())
+}
+
+/// Runs a blocking filesystem call, giving up after `timeout` and retrying failures that may be
+/// transient. Calls slower than `SLOW_FS_WARNING` are reported to `on_slow`.
+fn fs_call<T: Send + 'static>(
+    timeout: FsTimeout,
+    path: &Path,
+    operation: fn(&Path) -> io::Result<T>,
+    on_slow: &mut dyn FnMut(String),
+) -> io::Result<T> {
+    let mut attempt = 1;
+    loop {
+        let started = Instant::now();
+        let mut timed_out = false;
+        let result = match timeout.duration() {
+            None => operation(path),
+            Some(limit) => {
+                let (sender, receiver) = std::sync::mpsc::channel();
+                let owned_path = path.to_path_buf();
+                // A hung call can't be cancelled, so the thread is left to finish on its own.
+                std::thread::spawn(move || sender.send(operation(&owned_path)));
+                receiver.recv_timeout(limit).unwrap_or_else(|_| {
+                    timed_out = true;
+                    Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        strings::ERROR_FS_TIMED_OUT,
+                    ))
+                })
+            }
+        };
+
+        let elapsed = started.elapsed();
+        if elapsed >= SLOW_FS_WARNING {
+            let warning = format!(
+                "{}{}'{}{:.1}s",
+                strings::LABEL_PREFIX_SLOW_FS,
+                path.display(),
+                strings::LABEL_INFIX_SLOW_FS,
+                elapsed.as_secs_f32()
+            );
+            log::warn!("{warning}");
+            on_slow(warning);
+        }
+
+        match result {
+            // Retrying a call that hung would only wait as long again and strand another thread.
+            Err(e) if attempt < FS_ATTEMPTS && !timed_out && is_transient_io_error(&e) => {
+                log::debug!("Retrying '{}' after: {}", path.display(), e);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient_io_error(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+    )
+}
+
+/// Set by `--read-only`, which turns on read-only mode for one launch without changing the config.
+pub static READ_ONLY_LAUNCH: AtomicBool = AtomicBool::new(false);
+
+/// Numbers undo copies so every backup gets a unique file name.
+static UNDO_BACKUP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Number of `WriteTicket`s alive, i.e. disk operations on box art that haven't finished yet.
+pub static WRITES_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Id of the newest ticket on a path, and a receiver that fires once its operation is done.
+type PendingWrite = (u64, oneshot::Receiver<()>);
+
+static PENDING_WRITES: LazyLock<Mutex<HashMap<PathBuf, PendingWrite>>> =
+    LazyLock::new(Default::default);
+static NEXT_TICKET_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A place in the per-path queue of disk operations, so writes and deletes to the same box art
+/// file apply in the order they were issued rather than racing each other.
+pub struct WriteTicket {
+    path: PathBuf,
+    id: u64,
+    previous: Option<oneshot::Receiver<()>>,
+    // Dropping this lets the next operation on the same path proceed.
+    _done: oneshot::Sender<()>,
+}
+
+impl WriteTicket {
+    /// Must be called synchronously when the operation is issued to preserve ordering.
+    pub fn reserve(path: &Path) -> Self {
+        let (done, receiver) = oneshot::channel();
+        let id = NEXT_TICKET_ID.fetch_add(1, Ordering::SeqCst);
+        let previous = PENDING_WRITES
+            .lock()
+            .expect("Pending write queue was poisoned")
+            .insert(path.to_path_buf(), (id, receiver))
+            .map(|(_, x)| x);
+        WRITES_IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+
+        Self {
+            path: path.to_path_buf(),
+            id,
+            previous,
+            _done: done,
+        }
+    }
+
+    /// Waits until all earlier operations on the same path have finished.
+    pub async fn ready(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            // An error only means the earlier operation was dropped, which also frees the path.
+            let _ = previous.await;
+        }
+    }
+}
+
+impl Drop for WriteTicket {
+    fn drop(&mut self) {
+        // Nothing was queued behind this operation, so the path no longer needs an entry.
+        if let Ok(mut pending) = PENDING_WRITES.lock()
+            && pending
+                .get(&self.path)
+                .is_some_and(|(id, _)| *id == self.id)
+        {
+            pending.remove(&self.path);
+        }
+        WRITES_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Waits up to `timeout` for in-flight writes to finish, returning whether they all did.
+pub async fn wait_for_writes(timeout: Duration) -> bool {
+    let started = Instant::now();
+    while WRITES_IN_FLIGHT.load(Ordering::SeqCst) > 0 {
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+    }
+    true
+}
+
+/// Reads only the image header to get its dimensions.
+pub fn image_dimensions(image_path: &Path) -> Result<(u32, u32), String> {
+    ImageReader::open(image_path)
+        .map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_OPEN_IMAGE,
+                image_path.display(),
+                e
+            )
+        })?
+        .into_dimensions()
+        .map_err(|e| decode_error(image_path, e))
+}
+
+/// Matches `name` against `pattern`, where `*` stands for any run of characters and `?` for one.
+/// Case is ignored, since SD cards are usually FAT formatted.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and how much of `name` it has swallowed so far.
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Somewhere art can be looked up by ROM name. Fetching may block, so call it off the UI thread.
+pub trait ArtSource: Send + Sync {
+    /// Encoded image for the ROM named `rom_name`.
+    fn fetch(&self, rom_name: &str) -> Result<Vec<u8>, String>;
+
+    /// Tries each of `rom_names` in order, failing with the first name's error if none is found.
+    fn fetch_first(&self, rom_names: &[String]) -> Result<Vec<u8>, String> {
+        let mut first_error = None;
+        for rom_name in rom_names {
+            match self.fetch(rom_name) {
+                Ok(bytes) => return Ok(bytes),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.unwrap_or_default())
+    }
+}
+
+/// A folder of images named after the ROMs, such as an unpacked art pack.
+pub struct LocalFolderSource {
+    pub folder: PathBuf,
+}
+
+impl ArtSource for LocalFolderSource {
+    fn fetch(&self, rom_name: &str) -> Result<Vec<u8>, String> {
+        let path = ART_SOURCE_EXTENSIONS
+            .iter()
+            .map(|extension| self.folder.join(format!("{rom_name}.{extension}")))
+            .find(|x| x.is_file())
+            .ok_or_else(|| {
+                format!(
+                    "{}{}'{}{}'",
+                    strings::ERROR_PREFIX_ART_NOT_IN_SOURCE,
+                    rom_name,
+                    strings::ERROR_INFIX_ART_NOT_IN_SOURCE,
+                    self.folder.display()
+                )
+            })?;
+        std::fs::read(&path).map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_READ_ART_SOURCE,
+                path.display(),
+                e
+            )
+        })
+    }
+}
+
+/// A web service that serves a ROM's art at a URL built from its name.
+pub struct HttpSource {
+    /// URL with `{name}` standing for the ROM name.
+    pub url_template: String,
+}
+
+impl ArtSource for HttpSource {
+    fn fetch(&self, rom_name: &str) -> Result<Vec<u8>, String> {
+        download(
+            &self
+                .url_template
+                .replace(ART_SOURCE_NAME_PLACEHOLDER, &percent_encode(rom_name)),
+        )
+    }
+}
+
+/// Escapes `text` for use in a URL path or query, keeping only unreserved characters as is.
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|x| match x {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(x).to_string()
+            }
+            _ => format!("%{x:02X}"),
+        })
+        .collect()
+}
+
+/// Downloads and decodes the image at `url`. Blocks, so call it off the UI thread.
+pub fn download_image(url: &str) -> Result<DynamicImage, String> {
+    let bytes = download(url)?;
+    ::image::load_from_memory(&bytes).map_err(|e| decode_error(Path::new(url), e))
+}
+
+/// Downloads the image at `url` without decoding it. Blocks, so call it off the UI thread.
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    log::info!("Downloading image from {url}");
+    let download_error =
+        |e: ureq::Error| format!("{}{}': {}", strings::ERROR_PREFIX_DOWNLOAD, url, e);
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(DOWNLOAD_TIMEOUT))
+        .build()
+        .into();
+    let mut response = agent.get(url).call().map_err(download_error)?;
+
+    if let Some(mime_type) = response.body().mime_type()
+        && !mime_type.starts_with("image/")
+    {
+        return Err(format!(
+            "{}{}'{}{}",
+            strings::ERROR_PREFIX_URL_NOT_IMAGE,
+            url,
+            strings::ERROR_SUFFIX_URL_NOT_IMAGE,
+            mime_type
+        ));
+    }
+
+    response
+        .body_mut()
+        .with_config()
+        .limit(DOWNLOAD_MAX_BYTES)
+        .read_to_vec()
+        .map_err(download_error)
+}
+
+/// The format of an image file, going by its contents rather than its extension.
+fn image_format(path: &Path) -> Option<ImageFormat> {
+    ImageReader::open(path)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .format()
+}
+
+/// Reads an image's width and height from its header, without decoding the pixels.
+fn read_image_dimensions(path: &Path) -> io::Result<(u32, u32)> {
+    ImageReader::open(path)?
+        .with_guessed_format()?
+        .into_dimensions()
+        .map_err(io::Error::other)
+}
+
+pub fn open_image(path: &Path) -> Result<DynamicImage, String> {
+    ImageReader::open(path)
+        .map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_OPEN_IMAGE,
+                path.display(),
+                e
+            )
+        })?
+        .decode()
+        .map_err(|e| decode_error(path, e))
+}
+
+/// Like `open_image`, for art on the card, where the read buffer size matters.
+pub fn open_buffered_image(
+    path: &Path,
+    read_buffer: ReadBufferSize,
+) -> Result<DynamicImage, String> {
+    let file = File::open(path).map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_OPEN_IMAGE,
+            path.display(),
+            e
+        )
+    })?;
+    ImageReader::new(BufReader::with_capacity(read_buffer.bytes(), file))
+        .with_guessed_format()
+        .map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_GUESS_FORMAT,
+                path.display(),
+                e
+            )
+        })?
+        .decode()
+        .map_err(|e| decode_error(path, e))
+}
+
+/// Decodes `boxart_path` and shrinks it to fit in `size`. With `persist`, the thumbnail is also
+/// saved to the thumbnail cache, and read back from there for as long as the art is unchanged.
+pub fn load_thumbnail(
+    boxart_path: &Path,
+    size: u32,
+    read_buffer: ReadBufferSize,
+    persist: bool,
+) -> Result<DynamicImage, String> {
+    let cached_path = persist
+        .then(|| cached_thumbnail_path(boxart_path, size))
+        .flatten();
+    if let Some(cached_path) = &cached_path
+        && let Ok(thumbnail) = ::image::open(cached_path)
+    {
+        return Ok(thumbnail);
+    }
+
+    let thumbnail = open_buffered_image(boxart_path, read_buffer)?.thumbnail(size, size);
+    if let Some(cached_path) = cached_path {
+        // The cache only saves time, so failing to write it isn't worth reporting.
+        if let Err(e) = cached_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .map_err(ImageError::IoError)
+            .and_then(|()| thumbnail.save_with_format(&cached_path, ImageFormat::Png))
+        {
+            log::warn!(
+                "Failed to cache thumbnail at '{}': {e}",
+                cached_path.display()
+            );
+        }
+    }
+    Ok(thumbnail)
+}
+
+/// Where the thumbnail of `boxart_path` is cached, named after the art's path, size and
+/// modification time so edited art gets a new thumbnail.
+fn cached_thumbnail_path(boxart_path: &Path, size: u32) -> Option<PathBuf> {
+    let metadata = std::fs::metadata(boxart_path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    (boxart_path, metadata.len(), metadata.modified().ok(), size).hash(&mut hasher);
+    Some(
+        thumbnail_cache_dir()
+            .ok()?
+            .join(format!("{:016x}.png", hasher.finish())),
+    )
+}
+
+/// Deletes every persisted thumbnail.
+pub fn clear_thumbnail_cache() -> Result<(), String> {
+    let cache_dir = thumbnail_cache_dir()?;
+    match std::fs::remove_dir_all(&cache_dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_CLEAR_THUMBNAIL_CACHE,
+            cache_dir.display(),
+            write_error_detail(&e)
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The characters of a ROM name outside of bracketed and parenthesized tags like `(USA)` and `[!]`.
+fn untagged_chars(name: &str) -> impl Iterator<Item = char> + '_ {
+    let mut depth = 0usize;
+    name.chars().filter(move |c| match c {
+        '(' | '[' => {
+            depth += 1;
+            false
+        }
+        ')' | ']' => {
+            depth = depth.saturating_sub(1);
+            false
+        }
+        _ => depth == 0,
+    })
+}
+
+/// Removes tags from a ROM name and collapses the whitespace left behind, so
+/// "Tetris  (USA) [!]" becomes "Tetris".
+fn strip_name_tags(name: &str) -> String {
+    untagged_chars(name)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reduces a ROM name to what stays the same across regions and dumps: tags, case, and
+/// punctuation are dropped, so "Tetris (USA) [!]" matches "tetris (Europe)".
+fn normalized_rom_name(name: &str) -> String {
+    untagged_chars(name)
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Describes a failed write, pointing at read-only mounts and permissions when the OS refused it.
+pub fn write_error_detail(error: &io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => {
+            format!("{error}{}", strings::ERROR_SUFFIX_PERMISSION_DENIED)
+        }
+        _ => error.to_string(),
+    }
+}
+
+fn image_error_detail(error: &ImageError) -> String {
+    match error {
+        ImageError::IoError(e) => write_error_detail(e),
+        _ => error.to_string(),
+    }
+}
+
+/// Counts the frames of GIF, APNG, or WebP art, which decodes as just its first frame otherwise.
+pub fn animation_frame_count(
+    path: &Path,
+    format: ImageFormat,
+    read_buffer: ReadBufferSize,
+) -> Result<usize, ImageError> {
+    let reader = || File::open(path).map(|x| BufReader::with_capacity(read_buffer.bytes(), x));
+    Ok(match format {
+        ImageFormat::Gif => GifDecoder::new(reader()?)?.into_frames().count(),
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(reader()?)?;
+            if decoder.is_apng()? {
+                decoder.apng()?.into_frames().count()
+            } else {
+                1
+            }
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(reader()?)?;
+            if decoder.has_animation() {
+                decoder.into_frames().count()
+            } else {
+                1
+            }
+        }
+        _ => 1,
+    })
+}
+
+/// Formats a decode failure, spelling out when the image's format isn't supported by this build.
+pub fn decode_error(path: &Path, error: ImageError) -> String {
+    if let ImageError::Unsupported(unsupported) = &error
+        && let UnsupportedErrorKind::Format(hint) = unsupported.kind()
+    {
+        let format = match hint {
+            ImageFormatHint::Exact(format) => format!("{format:?}"),
+            ImageFormatHint::Name(name) => name,
+            ImageFormatHint::PathExtension(extension) => extension.to_string_lossy().to_uppercase(),
+            _ => {
+                return format!(
+                    "{}{}'{}",
+                    strings::ERROR_PREFIX_UNSUPPORTED_FORMAT,
+                    path.display(),
+                    strings::ERROR_SUFFIX_UNKNOWN_FORMAT
+                );
+            }
+        };
+
+        return format!(
+            "{}{}': {}{}",
+            strings::ERROR_PREFIX_UNSUPPORTED_FORMAT,
+            path.display(),
+            format,
+            strings::ERROR_SUFFIX_UNSUPPORTED_FORMAT
+        );
+    }
+
+    format!(
+        "{}{}': {}",
+        strings::ERROR_PREFIX_DECODE_IMAGE,
+        path.display(),
+        error
+    )
+}
+
+/// Encodes `img` in the format `path`'s extension names, so art is never mislabeled.
+/// `png_color_type` only applies to PNG, the format NextUI reads.
+fn save_image(img: DynamicImage, path: &Path, png_color_type: PngColorType) -> Result<(), String> {
+    match ImageFormat::from_path(path) {
+        Ok(ImageFormat::Png) => png_color_type.save(img, path),
+        Ok(format) => {
+            let img = match format {
+                // No alpha channel to keep.
+                ImageFormat::Jpeg => DynamicImage::ImageRgb8(img.into_rgb8()),
+                _ => img,
+            };
+            img.save_with_format(path, format)
+                .map_err(|e| image_error_detail(&e))
+        }
+        Err(e) => Err(image_error_detail(&e)),
+    }
+}
+
+/// Writes `img` to `boxart_path` after applying the import settings, in the format its extension
+/// names, returning the file size.
+pub fn save_boxart(
+    img: DynamicImage,
+    boxart_path: &Path,
+    config: &PersistentConfig,
+) -> Result<u64, String> {
+    prepare_boxart_write(boxart_path, config)?;
+    save_image(
+        config.import_resize.apply(img, config.resize_filter),
+        boxart_path,
+        config.png_color_type,
+    )
+    .map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_SAVE_IMAGE,
+            boxart_path.display(),
+            e
+        )
+    })?;
+
+    std::fs::metadata(boxart_path)
+        .map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_GET_METADATA_SAVED,
+                boxart_path.display(),
+                e
+            )
+        })
+        .map(|m| m.len())
+}
+
+/// Opens box art with `editor_command`, or the system's default app for images if it's empty.
+pub fn open_in_editor(boxart_path: &Path, editor_command: &str) -> Result<(), String> {
+    let open_error = |e: &dyn std::fmt::Display| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_OPEN_EDITOR,
+            boxart_path.display(),
+            e
+        )
+    };
+    // Split like a shell would, so quoted programs and arguments can contain spaces.
+    let parts = shell_words::split(editor_command).map_err(|e| open_error(&e))?;
+    let mut command = match parts.split_first() {
+        Some((program, args)) => {
+            let mut command = std::process::Command::new(program);
+            command.args(args);
+            command
+        }
+        // Not `cmd /C start`, which would treat characters like `&` in the path as commands.
+        None if cfg!(target_os = "windows") => std::process::Command::new("explorer"),
+        None if cfg!(target_os = "macos") => std::process::Command::new("open"),
+        None => std::process::Command::new("xdg-open"),
+    };
+
+    let mut child = command
+        .arg(boxart_path)
+        .spawn()
+        .map_err(|e| open_error(&e))?;
+    // Reaped in the background so the editor doesn't linger as a zombie process once closed.
+    std::thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+/// Where copies of replaced art are kept for undo. Removed when the app closes.
+pub fn undo_folder() -> PathBuf {
+    std::env::temp_dir().join(format!("nextart-undo-{}", std::process::id()))
+}
+
+/// Copies the art at `boxart_path` into the undo folder, returning `None` if there is no art.
+fn backup_art(boxart_path: &Path) -> Result<Option<PathBuf>, String> {
+    match std::fs::metadata(boxart_path) {
+        Ok(metadata) if metadata.is_file() => {}
+        Ok(_) => return Ok(None),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_GET_METADATA,
+                boxart_path.display(),
+                e
+            ));
+        }
+    }
+
+    let folder = undo_folder();
+    let backup = folder.join(format!(
+        "{}.bak",
+        UNDO_BACKUP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&folder)
+        .and_then(|()| std::fs::copy(boxart_path, &backup))
+        .map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_BACKUP_ART,
+                boxart_path.display(),
+                e
+            )
+        })?;
+
+    Ok(Some(backup))
+}
+
+/// Runs `write` on a ROM's box art, keeping copies of the art before and after so it can be undone.
+pub fn write_undoable(
+    rom_index: usize,
+    boxart_path: &Path,
+    write: impl FnOnce() -> Result<u64, String>,
+) -> Result<(ArtChange, u64), String> {
+    let before = backup_art(boxart_path)?;
+    let size = write()?;
+    let after = backup_art(boxart_path)?;
+
+    Ok((
+        ArtChange {
+            rom_index,
+            boxart_path: boxart_path.to_path_buf(),
+            before,
+            after,
+        },
+        size,
+    ))
+}
+
+/// Puts a copy made by `backup_art` back in place, or deletes the art if there was none.
+pub fn restore_art(boxart_path: &Path, version: Option<&Path>) -> Result<u64, String> {
+    let result = match version {
+        Some(backup) => {
+            create_boxart_folder(boxart_path)?;
+            std::fs::copy(backup, boxart_path)
+        }
+        None => match std::fs::remove_file(boxart_path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(0),
+        },
+    };
+
+    result.map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_RESTORE_ART,
+            boxart_path.display(),
+            write_error_detail(&e)
+        )
+    })
+}
+
+/// Moves art to `to`, refusing to replace a file that's already there. Art given an extension of
+/// another format is re-encoded in that format, so it isn't left mislabeled.
+pub fn rename_art(from: &Path, to: &Path, png_color_type: PngColorType) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!(
+            "{}{}'{}",
+            strings::ERROR_PREFIX_BOXART_EXISTS,
+            to.display(),
+            strings::ERROR_SUFFIX_BOXART_EXISTS
+        ));
+    }
+    if ImageFormat::from_path(from).ok() != ImageFormat::from_path(to).ok() {
+        save_image(open_image(from)?, to, png_color_type).map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_SAVE_IMAGE,
+                to.display(),
+                e
+            )
+        })?;
+        return std::fs::remove_file(from).map_err(|e| {
+            format!(
+                "{}{}': {}",
+                strings::ERROR_PREFIX_DELETE_FILE,
+                from.display(),
+                write_error_detail(&e)
+            )
+        });
+    }
+    std::fs::rename(from, to).map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_RENAME_BOXART,
+            from.display(),
+            write_error_detail(&e)
+        )
+    })
+}
+
+/// Where the art a write replaces is kept with `keep_previous_art`, e.g. `game.prev.png` for `game.png`.
+pub fn previous_art_path(boxart_path: &Path) -> PathBuf {
+    let mut file_name = boxart_path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(PREVIOUS_ART_INFIX);
+    if let Some(extension) = boxart_path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    boxart_path.with_file_name(file_name)
+}
+
+/// Gets `boxart_path` ready to be overwritten: creates its folder and keeps the current art if enabled.
+fn prepare_boxart_write(boxart_path: &Path, config: &PersistentConfig) -> Result<(), String> {
+    create_boxart_folder(boxart_path)?;
+    if !config.keep_previous_art {
+        return Ok(());
+    }
+
+    let previous_path = previous_art_path(boxart_path);
+    match std::fs::copy(boxart_path, &previous_path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_KEEP_PREVIOUS_ART,
+            previous_path.display(),
+            write_error_detail(&e)
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Creates the folder `boxart_path` goes in, which may not exist yet if media folders are created lazily.
+fn create_boxart_folder(boxart_path: &Path) -> Result<(), String> {
+    let Some(folder) = boxart_path.parent() else {
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(folder).map_err(|e| {
+        format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_MEDIA_DIR,
+            folder.display(),
+            write_error_detail(&e)
+        )
+    })
+}
+
+pub fn delete_boxart(boxart_path: &Path) -> Result<u64, String> {
+    match std::fs::remove_file(boxart_path) {
+        Ok(()) => Ok(0),
+        Err(e) => Err(format!(
+            "{}{}': {}",
+            strings::ERROR_PREFIX_DELETE_FILE,
+            boxart_path.display(),
+            write_error_detail(&e)
+        )),
+    }
+}
+
+/// Writes `img` as a palette PNG, with transparency kept per palette entry.
+fn save_indexed_png(img: &RgbaImage, path: &Path) -> Result<(), String> {
+    let (palette, indices) = quantize(img);
+    let png_error = |e: png::EncodingError| match e {
+        png::EncodingError::IoError(e) => write_error_detail(&e),
+        e => e.to_string(),
+    };
+
+    let file = File::create(path).map_err(|e| write_error_detail(&e))?;
+    let mut encoder = png::Encoder::new(file, img.width(), img.height());
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(
+        palette
+            .iter()
+            .flat_map(|x| [x[0], x[1], x[2]])
+            .collect::<Vec<u8>>(),
+    );
+    if palette.iter().any(|x| x[3] != u8::MAX) {
+        encoder.set_trns(palette.iter().map(|x| x[3]).collect::<Vec<u8>>());
+    }
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&indices).map_err(png_error)?;
+    writer.finish().map_err(png_error)
+}
+
+/// Reduces `img` to at most 256 colors, returning the palette and each pixel's index into it.
+/// Images that already fit keep their exact colors; others are split up by median cut.
+fn quantize(img: &RgbaImage) -> (Vec<[u8; 4]>, Vec<u8>) {
+    let mut counts: HashMap<[u8; 4], u64> = HashMap::new();
+    for pixel in img.pixels() {
+        *counts.entry(pixel.0).or_default() += 1;
+    }
+
+    let palette: Vec<[u8; 4]> = if counts.len() <= 256 {
+        counts.keys().copied().collect()
+    } else {
+        median_cut(counts.into_iter().collect(), 256)
+    };
+
+    let mut lookup: HashMap<[u8; 4], u8> = HashMap::new();
+    let indices = img
+        .pixels()
+        .map(|pixel| {
+            *lookup.entry(pixel.0).or_insert_with(|| {
+                let distance = |x: &[u8; 4]| -> u32 {
+                    (0..4)
+                        .map(|c| (x[c] as i32 - pixel.0[c] as i32).pow(2) as u32)
+                        .sum()
+                };
+                (0..palette.len())
+                    .min_by_key(|x| distance(&palette[*x]))
+                    .unwrap_or(0) as u8
+            })
+        })
+        .collect();
+
+    (palette, indices)
+}
+
+/// Splits the colors into `max` boxes along their widest channel and averages each box.
+fn median_cut(colors: Vec<([u8; 4], u64)>, max: usize) -> Vec<[u8; 4]> {
+    let widest_channel = |colors: &[([u8; 4], u64)]| {
+        (0..4)
+            .map(|c| {
+                let values = colors.iter().map(|(x, _)| x[c]);
+                let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                (c, range)
+            })
+            .max_by_key(|(_, range)| *range)
+            .unwrap_or((0, 0))
+    };
+
+    let mut boxes = vec![colors];
+    while boxes.len() < max {
+        let Some((index, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.len() > 1)
+            .map(|(i, x)| (i, widest_channel(x)))
+            .max_by_key(|(_, (_, range))| *range)
+            .map(|(i, (channel, _))| (i, channel))
+        else {
+            break;
+        };
+        let mut lower = boxes.swap_remove(index);
+        lower.sort_unstable_by_key(|(x, _)| x[channel]);
+        let upper = lower.split_off(lower.len() / 2);
+        boxes.push(lower);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|colors| {
+            let total: u64 = colors.iter().map(|(_, count)| count).sum();
+            let mut average = [0u8; 4];
+            for (c, channel) in average.iter_mut().enumerate() {
+                let sum: u64 = colors.iter().map(|(x, count)| x[c] as u64 * count).sum();
+                *channel = (sum / total.max(1)) as u8;
+            }
+            average
+        })
+        .collect()
+}
+
+/// Imports the image at `source` as box art. The file is copied as-is unless it needs processing.
+pub fn import_boxart_file(
+    source: &Path,
+    boxart_path: &Path,
+    config: &PersistentConfig,
+) -> Result<u64, String> {
+    // Copied as-is only when the file already is what the art's extension says.
+    if config.import_resize == ImportResize::Off
+        && config.png_color_type == PngColorType::Original
+        && image_format(source).is_some_and(|x| ImageFormat::from_path(boxart_path).ok() == Some(x))
+    {
+        prepare_boxart_write(boxart_path, config)?;
+        return std::fs::copy(source, boxart_path).map_err(|e| {
+            format!(
+                "{}{}' to '{}': {}",
+                strings::ERROR_PREFIX_COPY_FILE,
+                source.display(),
+                boxart_path.display(),
+                write_error_detail(&e)
+            )
+        });
+    }
+
+    save_boxart(open_image(source)?, boxart_path, config)
+}
+
+fn serialize_path_lossy<S: serde::Serializer>(
+    path: &Path,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rom(boxart_path: &str, boxart_size: u64) -> Rom {
+        let boxart_path = PathBuf::from(boxart_path);
+        let stem = boxart_path.file_stem().unwrap().to_os_string();
+        Rom {
+            boxart_size,
+            ..Rom::new(&stem, stem.to_string_lossy().into(), boxart_path)
+        }
+    }
+
+    #[test]
+    fn finished_writes_leave_no_queue_entry() {
+        let path = Path::new("/roms/GBA/.media/Queued.png");
+        let pending = || PENDING_WRITES.lock().unwrap().contains_key(path);
+
+        let first = WriteTicket::reserve(path);
+        let second = WriteTicket::reserve(path);
+        drop(first);
+        assert!(pending(), "the second write is still queued on the path");
+        drop(second);
+        assert!(!pending());
+    }
+
+    #[test]
+    fn reindex_replaces_the_previous_index_errors() {
+        let reindexed = |error: &str| {
+            let mut state = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+            state.errors.push(error.to_owned());
+            state.collection_errors.insert(String::from("GBA"), 1);
+            state
+        };
+        let mut state = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        state.errors.push(String::from("failed to save art"));
+
+        state.merge_reindexed(reindexed("unreadable note"));
+        state.merge_reindexed(reindexed("unreadable note"));
+        assert_eq!(state.errors, ["failed to save art", "unreadable note"]);
+        assert_eq!(state.collection_errors.get("GBA"), Some(&1));
+
+        state.merge_reindexed(State::new(
+            PathBuf::from("/roms"),
+            PersistentConfig::default(),
+        ));
+        assert_eq!(state.errors, ["failed to save art"]);
+        assert!(state.collection_errors.is_empty());
+    }
+
+    #[test]
+    fn gamelist_resolves_names_and_art() {
+        let gamelist = Gamelist::parse(
+            r#"<?xml version="1.0"?>
+            <gameList>
+                <game>
+                    <path>./Advance Wars (USA).gba</path>
+                    <name>Advance Wars</name>
+                    <image>./media/images/Advance Wars (USA).png</image>
+                </game>
+                <game>
+                    <path>./Tetris &amp; Co.gba</path>
+                    <thumbnail>/art/tetris.png</thumbnail>
+                </game>
+                <game>
+                    <path>./Hacks/Tetris &amp; Co.gba</path>
+                    <name>Tetris Hack</name>
+                </game>
+                <game>
+                    <name>No path, so it can't be matched to a ROM</name>
+                </game>
+            </gameList>"#,
+            Path::new("/roms/GBA"),
+        )
+        .expect("gamelist should parse");
+
+        assert_eq!(
+            gamelist.get(OsStr::new("Advance Wars (USA).gba")),
+            Some(&GamelistEntry {
+                name: Some(String::from("Advance Wars")),
+                image: Some(PathBuf::from(
+                    "/roms/GBA/media/images/Advance Wars (USA).png"
+                )),
+            })
+        );
+        assert_eq!(
+            gamelist.get(OsStr::new("Tetris & Co.gba")),
+            Some(&GamelistEntry {
+                name: None,
+                image: Some(PathBuf::from("/art/tetris.png")),
+            })
+        );
+        assert_eq!(gamelist.get(OsStr::new("Advance Wars (USA)")), None);
+        assert!(Gamelist::parse("<gameList>", Path::new("/roms/GBA")).is_err());
+    }
+
+    #[test]
+    fn boxart_file_name_fills_in_the_stem() {
+        let mut config = PersistentConfig::default();
+        assert_eq!(
+            config.boxart_file_name(OsStr::new("Tetris (USA)")),
+            "Tetris (USA).png"
+        );
+        config.boxart_template = String::from("{stem}-box.jpg");
+        assert_eq!(
+            config.boxart_file_name(OsStr::new("Tetris")),
+            "Tetris-box.jpg"
+        );
+
+        assert!(PersistentConfig::is_valid_boxart_template("{stem}-box.jpg"));
+        assert!(!PersistentConfig::is_valid_boxart_template("box.png"));
+        assert!(!PersistentConfig::is_valid_boxart_template(
+            "art/{stem}.png"
+        ));
+        assert!(!PersistentConfig::is_valid_boxart_template("{stem}.txt"));
+        assert!(!PersistentConfig::is_valid_boxart_template("..{stem}.png"));
+
+        config.boxart_template = String::from("../{stem}.png");
+        assert_eq!(
+            config.with_valid_boxart_template().boxart_template,
+            PersistentConfig::default_boxart_template()
+        );
+    }
+
+    #[test]
+    fn glob_matches_wildcards_ignoring_case() {
+        assert!(glob_matches("*", "GBA"));
+        assert!(glob_matches("gb?", "GBA"));
+        assert!(glob_matches("*-old", "SNES-OLD"));
+        assert!(glob_matches("a*b*c", "aXbYbZc"));
+        assert!(!glob_matches("gb?", "GB"));
+        assert!(!glob_matches("GB", "GBA"));
+        assert!(!glob_matches("*-old", "old-SNES"));
+    }
+
+    #[test]
+    fn normalized_rom_name_ignores_tags_case_and_punctuation() {
+        assert_eq!(
+            normalized_rom_name("Tetris (USA) [!]"),
+            normalized_rom_name("tetris (Europe)")
+        );
+        assert_eq!(
+            normalized_rom_name("Mario & Luigi: Superstar Saga"),
+            "marioluigisuperstarsaga"
+        );
+        assert_ne!(
+            normalized_rom_name("Tetris 2"),
+            normalized_rom_name("Tetris")
+        );
+    }
+
+    #[test]
+    fn suggest_art_renames_pairs_only_unambiguous_matches() {
+        let index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Tetris (USA).png", 0),
+                rom("/roms/GBA/.media/Metroid.png", 10),
+                rom("/roms/GBA/.media/Golden Sun (USA).png", 0),
+                rom("/roms/GBA/.media/Golden Sun (Europe).png", 0),
+                rom("/roms/GBA/.media/Zelda.png", 0),
+            ],
+            collections: vec![Collection {
+                name: String::from("GBA"),
+                rom_indices: vec![0, 1, 2, 3, 4],
+                manifest: None,
+                no_media: false,
+                media_folder: PathBuf::from("/roms/GBA/.media"),
+            }],
+        };
+        let orphans = [
+            (PathBuf::from("/roms/GBA/.media/tetris.png"), 1),
+            // Metroid already has art.
+            (PathBuf::from("/roms/GBA/.media/Metroid (USA).png"), 1),
+            // Could be either Golden Sun.
+            (PathBuf::from("/roms/GBA/.media/Golden Sun.png"), 1),
+            // Two candidates for the one Zelda.
+            (PathBuf::from("/roms/GBA/.media/Zelda (USA).png"), 1),
+            (PathBuf::from("/roms/GBA/.media/Zelda [!].png"), 1),
+            // Art of the same name in another collection's media folder.
+            (PathBuf::from("/roms/GB/.media/Tetris.png"), 1),
+        ];
+
+        assert_eq!(
+            suggest_art_renames(&orphans, &index),
+            [(PathBuf::from("/roms/GBA/.media/tetris.png"), 0)]
+        );
+    }
+
+    #[test]
+    fn strip_tags_keeps_names_that_are_only_tags() {
+        assert_eq!(strip_name_tags("Tetris  (USA) [!]"), "Tetris");
+        assert_eq!(strip_name_tags("Pokemon (USA) Red"), "Pokemon Red");
+        assert_eq!(strip_name_tags("Nested ((Beta) 2)"), "Nested");
+
+        assert_eq!(NameNormalization::Off.apply("Tetris (USA)"), "Tetris (USA)");
+        assert_eq!(NameNormalization::StripTags.apply("Tetris (USA)"), "Tetris");
+        assert_eq!(
+            NameNormalization::StripTags.apply("(Homebrew)"),
+            "(Homebrew)"
+        );
+    }
+
+    #[test]
+    fn rescan_keeps_the_indices_of_roms_still_there() {
+        let gba = |rom_indices| Collection {
+            name: String::from("GBA"),
+            rom_indices,
+            manifest: None,
+            no_media: false,
+            media_folder: PathBuf::from("/roms/GBA/.media"),
+        };
+        let mut state = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        state.index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Tetris.png", 0),
+                rom("/roms/GBA/.media/Metroid.png", 0),
+            ],
+            collections: vec![gba(vec![0, 1])],
+        };
+        let mut rescanned = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        rescanned.index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Zelda.png", 0),
+                rom("/roms/GBA/.media/Tetris.png", 10),
+            ],
+            collections: vec![gba(vec![0, 1])],
+        };
+
+        assert_eq!(state.merge_rescanned_collection("GBA", rescanned), [2, 0]);
+        assert_eq!(state.index.collections[0].rom_indices, [2, 0]);
+        assert_eq!(state.index.roms[0].boxart_size, 10);
+        // Metroid is gone from the folder but keeps its slot, unlisted.
+        assert_eq!(state.index.roms[1].name, "Metroid");
+        assert_eq!(state.index.roms[2].name, "Zelda");
+    }
+
+    #[test]
+    fn gamelist_paths_resolve_against_the_collection_folder() {
+        let collection = Path::new("/roms/GBA");
+        assert_eq!(
+            resolve_gamelist_path("./media/Tetris.png", collection),
+            Path::new("/roms/GBA/media/Tetris.png")
+        );
+        assert_eq!(
+            resolve_gamelist_path("media/Tetris.png", collection),
+            Path::new("/roms/GBA/media/Tetris.png")
+        );
+        assert_eq!(
+            resolve_gamelist_path("/art/Tetris.png", collection),
+            Path::new("/art/Tetris.png")
+        );
+        let home = directories::BaseDirs::new().map(|x| x.home_dir().to_path_buf());
+        assert_eq!(
+            resolve_gamelist_path("~/art/Tetris.png", collection),
+            home.unwrap_or_else(|| collection.to_path_buf())
+                .join("art/Tetris.png")
+        );
+    }
+
+    #[test]
+    fn split_systems_keep_the_art_root_folder_they_were_indexed_from() {
+        let root = std::env::temp_dir().join(format!("nextart-art-root-{}", std::process::id()));
+        let roms_folder = root.join("Mixed");
+        let art_root = root.join("Art");
+        std::fs::create_dir_all(&roms_folder).unwrap();
+        std::fs::create_dir_all(art_root.join("Mixed")).unwrap();
+        std::fs::create_dir_all(art_root.join("GB")).unwrap();
+        std::fs::write(roms_folder.join("Tetris.gb"), "").unwrap();
+        std::fs::write(roms_folder.join("Metroid.gba"), "").unwrap();
+        std::fs::write(art_root.join("Mixed/Tetris.png"), "art").unwrap();
+        std::fs::write(art_root.join("Mixed/Zelda.png"), "art").unwrap();
+        // Another library's art that happens to sit where a "GB" collection would keep its own.
+        std::fs::write(art_root.join("GB/Pokemon.png"), "art").unwrap();
+
+        let mut state = State::new(
+            roms_folder,
+            PersistentConfig {
+                art_root: Some(art_root.clone()),
+                group_by_extension: true,
+                ..PersistentConfig::default()
+            },
+        );
+        state.index_roms(&mut |_| {}).unwrap();
+        let names: Vec<&str> = state
+            .index
+            .collections
+            .iter()
+            .map(|x| x.name.as_str())
+            .collect();
+        assert_eq!(names, ["GB", "GBA"]);
+        assert!(
+            state
+                .index
+                .collections
+                .iter()
+                .all(|x| x.media_folder == art_root.join("Mixed"))
+        );
+
+        let media_folders = state
+            .index
+            .collections
+            .iter()
+            .map(|x| x.media_folder.clone())
+            .collect();
+        let boxart_paths = state
+            .index
+            .listed_roms()
+            .map(|(_, x)| x.boxart_path.clone())
+            .collect();
+        let orphans = find_orphaned_art(&media_folders, &boxart_paths);
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(orphans, [(art_root.join("Mixed/Zelda.png"), 3)]);
+    }
+
+    #[test]
+    fn reindex_keeps_writes_made_while_it_ran() {
+        let gba = |rom_indices| Collection {
+            name: String::from("GBA"),
+            rom_indices,
+            manifest: None,
+            no_media: false,
+            media_folder: PathBuf::from("/roms/GBA/.media"),
+        };
+        let mut state = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        state.index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Tetris.png", 0),
+                rom("/roms/GBA/.media/Metroid.png", 0),
+            ],
+            collections: vec![gba(vec![0, 1])],
+        };
+        state.mark_rom_written(1);
+        let mut reindexed = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        reindexed.write_count = state.write_count;
+        reindexed.index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Tetris.png", 5),
+                rom("/roms/GBA/.media/Metroid.png", 5),
+            ],
+            collections: vec![gba(vec![0, 1])],
+        };
+
+        // Art saved for Tetris while the scan still saw the old, missing art.
+        state.index.roms[0].boxart_size = 20;
+        state.index.roms[0].note = String::from("favorite");
+        state.mark_rom_written(0);
+        state.merge_reindexed(reindexed);
+        assert_eq!(state.index.roms[0].boxart_size, 20);
+        assert_eq!(state.index.roms[0].note, "favorite");
+        // Written before the scan started, so it read the newer art.
+        assert_eq!(state.index.roms[1].boxart_size, 5);
+    }
+
+    #[test]
+    fn merges_give_slots_of_roms_gone_earlier_to_new_roms() {
+        let gba = |rom_indices| Collection {
+            name: String::from("GBA"),
+            rom_indices,
+            manifest: None,
+            no_media: false,
+            media_folder: PathBuf::from("/roms/GBA/.media"),
+        };
+        let scan = |names: &[&str]| {
+            let mut scan = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+            scan.index = Index {
+                roms: names
+                    .iter()
+                    .map(|x| rom(&format!("/roms/GBA/.media/{x}.png"), 0))
+                    .collect(),
+                collections: vec![gba((0..names.len()).collect())],
+            };
+            scan
+        };
+        let mut state = scan(&["Tetris", "Metroid"]);
+        state.recently_modified.push(0);
+
+        // Tetris is gone, but the list may still have it selected, so Zelda doesn't take its slot.
+        state.merge_reindexed(scan(&["Metroid", "Zelda"]));
+        assert_eq!(state.index.collections[0].rom_indices, [1, 2]);
+        state.merge_reindexed(scan(&["Metroid", "Zelda", "Kirby"]));
+        assert_eq!(state.index.collections[0].rom_indices, [1, 2, 0]);
+        assert_eq!(state.index.roms.len(), 3);
+        assert_eq!(state.index.roms[0].name, "Kirby");
+        assert!(state.recently_modified.is_empty());
+    }
+
+    #[test]
+    fn config_round_trips_through_toml() {
+        let mut config = PersistentConfig {
+            art_root: Some(PathBuf::from("/art")),
+            ..PersistentConfig::default()
+        };
+        config
+            .verified_roms
+            .entry(String::from("GBA"))
+            .or_default()
+            .insert(String::from("Tetris"));
+        let serialized = toml::to_string_pretty(&config).expect("config should serialize");
+        let parsed: PersistentConfig = toml::from_str(&serialized).expect("config should parse");
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&config).unwrap()
+        );
+    }
+
+    #[test]
+    fn stale_boxart_path_count_follows_collection_manifests() {
+        let collection = |name: &str, rom_indices, manifest| Collection {
+            name: String::from(name),
+            rom_indices,
+            manifest,
+            no_media: false,
+            media_folder: PathBuf::from(format!("/roms/{name}/.media")),
+        };
+        let mut state = State::new(PathBuf::from("/roms"), PersistentConfig::default());
+        state.index = Index {
+            roms: vec![
+                rom("/roms/GBA/.media/Tetris.png", 10),
+                rom("/roms/GBA/.media/Metroid.jpg", 10),
+                rom("/roms/GB/.media/Tetris.jpg", 10),
+            ],
+            collections: vec![
+                collection("GBA", vec![0, 1], None),
+                collection(
+                    "GB",
+                    vec![2],
+                    Some(CollectionManifest {
+                        boxart_template: Some(String::from("{stem}.jpg")),
+                        max_art_size: None,
+                    }),
+                ),
+            ],
+        };
+
+        assert_eq!(state.stale_boxart_path_count(), 1);
+        state.config.boxart_template = String::from("{stem}.jpg");
+        assert_eq!(state.stale_boxart_path_count(), 1);
+    }
+
+    #[test]
+    fn rename_art_reencodes_for_another_format() {
+        let folder = std::env::temp_dir().join(format!("nextart-rename-{}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        let png = folder.join("Tetris.png");
+        let jpg = folder.join("Tetris.jpg");
+        DynamicImage::new_rgba8(4, 4).save(&png).unwrap();
+
+        rename_art(&png, &jpg, PngColorType::Original).unwrap();
+        assert!(!png.exists());
+        assert_eq!(image_format(&jpg), Some(ImageFormat::Jpeg));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+
+    #[test]
+    fn open_in_editor_rejects_unbalanced_quotes() {
+        let error =
+            open_in_editor(Path::new("/roms/GBA/.media/Tetris.png"), "\"my editor").unwrap_err();
+        assert!(error.starts_with(strings::ERROR_PREFIX_OPEN_EDITOR));
+    }
+}
@@ -1,24 +1,50 @@
 use std::{
-    fs::{DirEntry, File},
-    io::BufReader,
-    path::PathBuf,
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        Arc, LazyLock, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
 };
 
-use ::image::{EncodableLayout, ImageReader, RgbaImage};
+use ::image::{DynamicImage, ImageFormat, ImageReader, RgbaImage};
 use arboard::{Clipboard, ImageData};
 use bittenhumans::ByteSizeFormatter;
-use directories::ProjectDirs;
 use iced::{
-    Alignment, Element, Font, Length, Task,
+    Alignment, Element, Event, Font, Length, Padding, Point, Subscription, Task,
     alignment::Horizontal,
-    clipboard,
+    clipboard, event,
     font::Weight,
-    widget::{Space, button, column, image, row, scrollable, text, text_input},
+    futures::SinkExt,
+    keyboard, mouse, widget,
+    widget::{
+        Space, button, checkbox, column, container, horizontal_rule, image, mouse_area, pick_list,
+        progress_bar, row, rule, scrollable, slider, stack, text, text_input, tooltip,
+        vertical_rule,
+    },
+    window,
 };
 use rfd::FileDialog;
-use serde::{Deserialize, Serialize};
 
-mod strings;
+use nextart::{
+    AppTheme, ArtAdjustment, ArtChange, ArtRename, ArtSourceKind, ArtTransparency,
+    BatchWriteResults, BoxartRenameResults, ClipboardAlpha, Collection, CoverageSnapshot,
+    DEFAULT_BOXART_TEMPLATE, DefaultRomAction, FsTimeout, IMPORT_JOURNAL_SAVE_INTERVAL,
+    ImportJournal, ImportResize, Index, ListDensity, MediaFolderCreation, Megapixels,
+    NameNormalization, OrphanRenameResults, PersistentConfig, PngColorType, PreviewGuides,
+    PreviewSize, READ_ONLY_LAUNCH, ReadBufferSize, RenameRestoreResults, ResizeFilter,
+    RestoreResults, Rom, RomLayout, SizeUnits, ThumbnailCacheLimit, UiScale, WRITES_IN_FLIGHT,
+    WriteTicket, animation_frame_count, clear_thumbnail_cache, decode_error, delete_boxart,
+    download_image, find_orphaned_art, image_dimensions, import_boxart_file, load_thumbnail,
+    open_buffered_image, open_image, open_in_editor, rename_art, restore_art, save_boxart, strings,
+    suggest_art_renames, undo_folder, wait_for_writes, write_error_detail, write_note,
+    write_undoable,
+};
 
 // UI Constants
 const PADDING_STANDARD: u16 = 30;
@@ -31,29 +57,184 @@ const SPACING_SMALL: u16 = 10;
 const SPACING_TINY: u16 = 5;
 
 const FONT_SIZE_TITLE: u16 = 32;
+const FONT_SIZE_SUBTITLE: u16 = 24;
 
-#[derive(Debug, Default, Clone)]
-struct Index {
-    roms: Vec<Rom>,
-    collections: Vec<Collection>,
+const CONTEXT_MENU_WIDTH: u16 = 160;
+const SHORTCUT_KEYS_WIDTH: u16 = 140;
+const UNDO_LIMIT: usize = 50;
+const CLIPBOARD_HISTORY_LIMIT: usize = 6;
+const CLIPBOARD_HISTORY_THUMBNAIL_SIZE: u32 = 64;
+const TOAST_WIDTH: u16 = 400;
+const PANE_DIVIDER_WIDTH: f32 = 8.0;
+/// Resolution of the pane ratio when laid out, as the panes' combined `FillPortion`.
+const PANE_PORTIONS: u16 = 1000;
+/// Narrowest share of the width either pane of the ROM list can be dragged down to.
+const MIN_PANE_RATIO: f32 = 0.2;
+const GALLERY_THUMBNAIL_SIZE: u32 = 128;
+const GALLERY_TILE_WIDTH: u16 = 160;
+const ROM_NAME_MAX_CHARS: usize = 60;
+const GALLERY_NAME_MAX_CHARS: usize = 18;
+const COLLECTION_NAME_MAX_CHARS: usize = 40;
+const ROM_SEARCH_INPUT_ID: &str = "rom-search";
+const RENAME_COLLECTION_INPUT_ID: &str = "rename-collection";
+
+const SETTINGS_EXPORT_FILE_NAME: &str = "nextart-settings.json";
+const LOG_ENV_VAR: &str = "NEXTART_LOG";
+const LOG_FILE_ENV_VAR: &str = "NEXTART_LOG_FILE";
+const READ_ONLY_FLAG: &str = "--read-only";
+
+const CLIPBOARD_ATTEMPTS: u32 = 3;
+const CLIPBOARD_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// Largest side of the downscaled copy that brightness/contrast previews are rendered from.
+const ADJUSTMENT_PREVIEW_SIZE: u32 = 512;
+const CONFIG_SAVE_DELAY: Duration = Duration::from_millis(500);
+const EXTERNAL_EDIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SHUTDOWN_WRITE_WAIT: Duration = Duration::from_secs(5);
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const TOAST_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Kind of failure an error message describes, told apart by the message's prefix.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ErrorCategory {
+    #[default]
+    All,
+    Filesystem,
+    Decode,
+    Clipboard,
+    Other,
 }
 
-#[derive(Debug, Clone)]
-struct Rom {
-    name: String,
-    boxart_path: PathBuf,
-    boxart_size: u64,
+impl ErrorCategory {
+    const ALL: [ErrorCategory; 5] = [
+        ErrorCategory::All,
+        ErrorCategory::Filesystem,
+        ErrorCategory::Decode,
+        ErrorCategory::Clipboard,
+        ErrorCategory::Other,
+    ];
+
+    fn of(error: &str) -> Self {
+        const CLIPBOARD: [&str; 6] = [
+            strings::ERROR_PREFIX_ACCESS_CLIPBOARD,
+            strings::ERROR_PREFIX_CLIPBOARD_IMAGE,
+            strings::ERROR_FAILED_CLIPBOARD_IMAGE_OTHER,
+            strings::ERROR_PREFIX_COPY_TO_CLIPBOARD,
+            strings::ERROR_PREFIX_URL_NOT_IMAGE,
+            strings::ERROR_PREFIX_DOWNLOAD,
+        ];
+        const DECODE: [&str; 4] = [
+            strings::ERROR_PREFIX_DECODE_IMAGE,
+            strings::ERROR_PREFIX_GUESS_FORMAT,
+            strings::ERROR_PREFIX_READ_DIMENSIONS,
+            strings::ERROR_PREFIX_UNSUPPORTED_FORMAT,
+        ];
+        const FILESYSTEM: [&str; 27] = [
+            strings::ERROR_FS_TIMED_OUT,
+            strings::ERROR_PREFIX_BACKUP_ART,
+            strings::ERROR_PREFIX_BOXART_EXISTS,
+            strings::ERROR_PREFIX_CLEAR_THUMBNAIL_CACHE,
+            strings::ERROR_PREFIX_COPY_FILE,
+            strings::ERROR_PREFIX_DELETE_FILE,
+            strings::ERROR_PREFIX_DIR_ENTRY,
+            strings::ERROR_PREFIX_DIR_READ,
+            strings::ERROR_PREFIX_DIR_TYPE,
+            strings::ERROR_PREFIX_FILE_STEM,
+            strings::ERROR_PREFIX_GET_METADATA,
+            strings::ERROR_PREFIX_INDEX_COLLECTION,
+            strings::ERROR_PREFIX_KEEP_PREVIOUS_ART,
+            strings::ERROR_PREFIX_LOSSY_FILE_NAME,
+            strings::ERROR_PREFIX_MEDIA_DIR,
+            strings::ERROR_PREFIX_MEDIA_NOT_DIR,
+            strings::ERROR_PREFIX_OPEN_IMAGE,
+            strings::ERROR_PREFIX_READ_COLLECTION,
+            strings::ERROR_PREFIX_READ_ART_SOURCE,
+            strings::ERROR_PREFIX_READ_GAMELIST,
+            strings::ERROR_PREFIX_READ_MANIFEST,
+            strings::ERROR_PREFIX_READ_NOTE,
+            strings::ERROR_PREFIX_RENAME_BOXART,
+            strings::ERROR_PREFIX_RENAME_COLLECTION,
+            strings::ERROR_PREFIX_RESTORE_ART,
+            strings::ERROR_PREFIX_SAVE_IMAGE,
+            strings::ERROR_PREFIX_SAVE_NOTE,
+        ];
+
+        let matches = |prefixes: &[&str]| prefixes.iter().any(|x| error.starts_with(x));
+        if matches(&CLIPBOARD) {
+            ErrorCategory::Clipboard
+        } else if matches(&DECODE) {
+            ErrorCategory::Decode
+        } else if matches(&FILESYSTEM) {
+            ErrorCategory::Filesystem
+        } else {
+            ErrorCategory::Other
+        }
+    }
+
+    fn matches(self, error: &str) -> bool {
+        self == ErrorCategory::All || self == Self::of(error)
+    }
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ErrorCategory::All => strings::LABEL_ERRORS_ALL,
+            ErrorCategory::Filesystem => strings::LABEL_ERRORS_FILESYSTEM,
+            ErrorCategory::Decode => strings::LABEL_ERRORS_DECODE,
+            ErrorCategory::Clipboard => strings::LABEL_ERRORS_CLIPBOARD,
+            ErrorCategory::Other => strings::LABEL_ERRORS_OTHER,
+        })
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct PersistentConfig {
-    roms_path: PathBuf,
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum VerifiedFilter {
+    #[default]
+    All,
+    Verified,
+    Unverified,
 }
 
-#[derive(Debug, Clone)]
-struct Collection {
-    name: String,
-    rom_indices: Vec<usize>,
+impl VerifiedFilter {
+    const ALL: [VerifiedFilter; 3] = [
+        VerifiedFilter::All,
+        VerifiedFilter::Verified,
+        VerifiedFilter::Unverified,
+    ];
+
+    fn matches(self, verified: bool) -> bool {
+        match self {
+            VerifiedFilter::All => true,
+            VerifiedFilter::Verified => verified,
+            VerifiedFilter::Unverified => !verified,
+        }
+    }
+}
+
+impl std::fmt::Display for VerifiedFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            VerifiedFilter::All => strings::LABEL_FILTER_ALL,
+            VerifiedFilter::Verified => strings::LABEL_FILTER_VERIFIED,
+            VerifiedFilter::Unverified => strings::LABEL_FILTER_UNVERIFIED,
+        })
+    }
+}
+
+/// Narrows down which ROMs a list view shows.
+#[derive(Debug, Default, Clone)]
+struct RomFilter {
+    search: String,
+    verified: VerifiedFilter,
+}
+
+impl RomFilter {
+    fn matches(&self, rom: &Rom, verified: bool) -> bool {
+        let search = self.search.trim().to_lowercase();
+        self.verified.matches(verified)
+            && (rom.display_name.to_lowercase().contains(&search)
+                || rom.name.to_lowercase().contains(&search))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -62,184 +243,617 @@ enum Message {
     OpenRomDirectoryPicker,
     OpenRomList(String, Vec<usize>),
     SelectRom(usize),
-    CompletedIndexing(State),
+    /// Selects a ROM the user opened, then runs the configured default action on it.
+    ManageRom(usize),
+    CompletedIndexing(Box<nextart::State>),
+    IndexingProgress(String),
+    CloseRequested(window::Id),
+    /// Writes were still running after waiting for them on close.
+    WritesStillPending(window::Id),
     RomDirectoryChosen(PathBuf),
+    OpenArtRootPicker,
+    ArtRootChosen(Option<PathBuf>),
+    ExportSettings,
+    ImportSettings,
+    ImportedSettings(Box<PersistentConfig>),
     OpenCollectionList,
     OpenErrorList,
+    SetErrorCategory(ErrorCategory),
+    SetGroupErrorsByCollection(bool),
     SetupDone(PathBuf),
     SetClipboardText(String),
+    /// Copies what is known about a ROM's art, for pasting into a bug report.
+    CopyRomDebugInfo(usize),
     SetClipboardImage(PathBuf),
     ReplacementImageFromClip(PathBuf, usize),
     ViewError(String),
     RecordError(String),
-    SetRomInfoImage(u32, u32, Vec<u8>),
+    /// Hides the error toast once it has been shown long enough.
+    ExpireToast(Instant),
+    DismissToast,
+    /// Decoded preview of the selected ROM's art, whether it has an alpha channel, and its frame count.
+    SetRomInfoImage(u32, u32, Vec<u8>, bool, usize),
     WroteNewImage(usize, u64),
+    /// Box art was written or deleted by the user, so the change can be undone.
+    ArtChanged(ArtChange, u64),
+    Undo,
+    Redo,
+    RestoredArt(RestoreResults),
+    RestoredRenames(RenameRestoreResults),
     ChooseReplacementImage(PathBuf, usize),
     ResetState,
+    /// Goes back to setup with the current Roms folder filled in, so another one can be picked.
+    ReturnToSetup,
     DeleteBoxart(PathBuf, usize),
+    RequestBulkDelete(Vec<usize>),
+    ConfirmBulkDelete,
+    CancelBulkDelete,
+    CursorMoved(Point),
+    StartResizingPanes,
+    /// Size of the window, to turn how far the pane divider is dragged into a ratio.
+    MeasuredPanes(iced::Size),
+    FinishResizingPanes,
+    OpenContextMenu(usize),
+    CloseContextMenu,
+    ContextMenuAction(Box<Message>),
+    /// Remembers the folder of a picked image, then handles the message that used it.
+    ImagePicked(PathBuf, Box<Message>),
+    OpenSettings,
+    SetSizeUnits(SizeUnits),
+    NavigateBack,
+    ToggleShortcutHelp,
+    FocusNext,
+    FocusPrevious,
+    /// Focuses the ROM list's search box, selecting what's in it so typing replaces it.
+    FocusSearch,
+    ToggleArtExempt(String),
+    TogglePinnedCollection(String),
+    StartDraggingCollection(String),
+    DragCollectionOver(String),
+    FinishDraggingCollection,
+    SetOnlyIncompleteCollections(bool),
+    /// Lists only the collections in a category, or all of them when empty.
+    SetCategoryFilter(String),
+    SetCollectionCategory(String, String),
+    SetSystemCategories(String),
+    StartRenamingCollection(String),
+    EditCollectionName(String),
+    CancelRenamingCollection,
+    RenameCollection(String, String),
+    RenamedCollection(String, String),
+    /// A collection rename was undone or redone, renaming it from the first name to the second.
+    RestoredCollectionName(String, String),
+    SetImportResize(ImportResize),
+    SetResizeFilter(ResizeFilter),
+    SetPngColorType(PngColorType),
+    SetDensity(ListDensity),
+    /// Adds the image to the clipboard history, then handles the message.
+    RememberClipboardImage(ClipboardImage, Box<Message>),
+    ReplacementImageFromHistory(PathBuf, usize, ClipboardImage),
+    RescanCollection,
+    RescannedCollection(String, Box<nextart::State>),
+    /// Indexes the whole Roms folder again in the background, without leaving the current view.
+    Reindex,
+    Reindexed(Box<nextart::State>),
+    SetArtTransparency(ArtTransparency),
+    FindMatchingRoms(usize),
+    ConfirmMatchingFill,
+    CancelMatchingFill,
+    DiscardMatchingFill,
+    KeepMatchingFill,
+    SetRomLayout(RomLayout),
+    ThumbnailLoaded(PathBuf, image::Handle),
+    SetTheme(AppTheme),
+    SetUiScale(UiScale),
+    ToggleRomSelection(usize, bool),
+    SelectRoms(Vec<usize>),
+    ClearRomSelection,
+    ApplyImageToSelected(ImageSource, Vec<usize>),
+    /// Replaces the box art of the ROM currently shown in the info pane.
+    ReplaceCurrentImage(ImageSource),
+    CompareArt(Box<PendingReplacement>),
+    /// Opens the existing box art of a ROM for brightness/contrast adjustment.
+    AdjustCurrentArt(usize),
+    SetArtAdjustment(ArtAdjustment),
+    ChooseCollectionDefaultArt(String),
+    ToggleVerified(usize),
+    SetRomNote(usize, String),
+    /// Selects the next ROM in the list without box art, wrapping around at the end.
+    SelectNextMissing,
+    SetVerifiedFilter(VerifiedFilter),
+    SetRomSearch(String),
+    SetCollectionDefaultArt(String, Option<PathBuf>),
+    ConfirmReplacement,
+    CancelReplacement,
+    AppliedImageToSelected(BatchWriteResults),
+    BulkProgress(usize, usize),
+    LargeClipboardImage(PathBuf, u32, u32),
+    ConfirmClipboardImage(PathBuf),
+    CancelClipboardImage,
+    SetClipboardWarnMegapixels(Megapixels),
+    SetBoxartTemplate(String),
+    SetExcludedCollections(String),
+    SetGroupByExtension(bool),
+    SetExtensionSystems(String),
+    /// Renames existing box art to match the current naming template.
+    MigrateBoxartTemplate,
+    MigratedBoxartTemplate(BoxartRenameResults),
+    SetPreviewMaxSize(PreviewSize),
+    SetThumbnailCacheLimit(ThumbnailCacheLimit),
+    SetPersistThumbnails(bool),
+    ClearThumbnailCache,
+    SetReadBufferSize(ReadBufferSize),
+    SetFsTimeout(FsTimeout),
+    SetMediaFolderCreation(MediaFolderCreation),
+    SetNameNormalization(NameNormalization),
+    SetDefaultRomAction(DefaultRomAction),
+    SetPreviewGuides(PreviewGuides),
+    CyclePreviewGuides,
+    SetClipboardAlpha(ClipboardAlpha),
+    SetReadOnly(bool),
+    SetEditorCommand(String),
+    SetArtSource(ArtSourceKind),
+    SetArtSourceLocation(String),
+    /// Looks up art for each ROM by name in the configured art source and writes what it finds.
+    FetchArtForSelected(Vec<usize>),
+    SetMergeCaseDuplicates(bool),
+    SetKeepPreviousArt(bool),
+    SetReadArtDimensions(bool),
+    SetReadGamelists(bool),
+    OpenInEditor(usize),
+    CheckExternalEdit,
+    ExternalEditChecked(usize, u64, Option<SystemTime>),
+    FindOrphanedArt,
+    OpenRecentlyModified,
+    CheckAllArt,
+    /// Identifies the check by when it started, then gives the files checked and the failures
+    /// among them as ROM indices and errors.
+    ArtCheckProgress(Instant, usize, Vec<(usize, String)>),
+    OpenRecentRom(usize),
+    FoundOrphanedArt(Vec<(PathBuf, u64)>),
+    DeleteOrphanedArt,
+    DeletedOrphanedArt(Vec<(PathBuf, Result<u64, String>)>),
+    RenameOrphanedArt,
+    RenamedOrphanedArt(OrphanRenameResults),
+}
+
+impl Message {
+    /// Whether handling this message starts a write to disk, which read-only mode refuses.
+    fn writes_to_disk(&self) -> bool {
+        matches!(
+            self,
+            Message::ExportSettings
+                | Message::ClearThumbnailCache
+                | Message::ReplacementImageFromClip(..)
+                | Message::ReplacementImageFromHistory(..)
+                | Message::ChooseReplacementImage(..)
+                | Message::DeleteBoxart(..)
+                | Message::ConfirmBulkDelete
+                | Message::StartRenamingCollection(_)
+                | Message::RenameCollection(..)
+                | Message::ApplyImageToSelected(..)
+                | Message::FetchArtForSelected(_)
+                | Message::ReplaceCurrentImage(_)
+                | Message::AdjustCurrentArt(_)
+                | Message::SetRomNote(..)
+                | Message::ConfirmReplacement
+                | Message::MigrateBoxartTemplate
+                | Message::DeleteOrphanedArt
+                | Message::RenameOrphanedArt
+                | Message::Undo
+                | Message::Redo
+                | Message::OpenInEditor(_)
+                | Message::ConfirmMatchingFill
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
-struct State {
-    roms_folder: PathBuf,
-    index: Index,
-    errors: Vec<String>,
+enum ImageSource {
+    Clipboard,
+    /// Asks the user to pick a file.
+    File,
+    /// A file that was already chosen, e.g. a collection's default art.
+    Path(PathBuf),
 }
 
-impl State {
-    pub fn index_roms(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let read_dir = std::fs::read_dir(&self.roms_folder).map_err(|e| {
-            format!(
-                "{}{}': {}",
-                strings::ERROR_PREFIX_DIR_READ,
-                self.roms_folder.display(),
-                e
-            )
-        })?;
-
-        for entry_result in read_dir {
-            if let Ok(entry) = entry_result {
-                let entry_path = entry.path();
-                match entry.file_type() {
-                    Ok(file_type) => {
-                        if file_type.is_dir() && !entry.path().ends_with(".media") {
-                            if let Err(e) = self.index_collection_folder(entry) {
-                                self.errors.push(format!(
-                                    "{}{}': {}",
-                                    strings::ERROR_PREFIX_INDEX_COLLECTION,
-                                    entry_path.display(),
-                                    e
-                                ));
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        self.errors.push(format!(
-                            "{}{}': {}",
-                            strings::ERROR_PREFIX_DIR_TYPE,
-                            entry.path().display(),
-                            e
-                        ));
-                    }
-                }
-            } else if let Err(e) = entry_result {
-                self.errors
-                    .push(format!("{}{}", strings::ERROR_PREFIX_DIR_ENTRY, e));
+/// A key binding, both matched by `key_press_message` and listed in the shortcut help.
+struct Shortcut {
+    key: ShortcutKey,
+    /// Ctrl, or Cmd on macOS.
+    command: bool,
+    shift: bool,
+    description: &'static str,
+    message: fn() -> Message,
+}
+
+enum ShortcutKey {
+    Named(keyboard::key::Named, &'static str),
+    Character(&'static str),
+}
+
+/// The first binding that matches a key press wins, so ones that need Shift come first.
+const SHORTCUTS: [Shortcut; 12] = [
+    Shortcut {
+        key: ShortcutKey::Named(keyboard::key::Named::Escape, "Esc"),
+        command: false,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_BACK,
+        message: || Message::NavigateBack,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("?"),
+        command: false,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_HELP,
+        message: || Message::ToggleShortcutHelp,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("f"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_SEARCH,
+        message: || Message::FocusSearch,
+    },
+    // Shift+Tab comes first, since the plain binding matches with Shift held too.
+    Shortcut {
+        key: ShortcutKey::Named(keyboard::key::Named::Tab, "Tab"),
+        command: false,
+        shift: true,
+        description: strings::LABEL_SHORTCUT_FOCUS_PREVIOUS,
+        message: || Message::FocusPrevious,
+    },
+    Shortcut {
+        key: ShortcutKey::Named(keyboard::key::Named::Tab, "Tab"),
+        command: false,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_FOCUS_NEXT,
+        message: || Message::FocusNext,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("v"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_PASTE,
+        message: || Message::ReplaceCurrentImage(ImageSource::Clipboard),
+    },
+    Shortcut {
+        key: ShortcutKey::Character("o"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_CHOOSE,
+        message: || Message::ReplaceCurrentImage(ImageSource::File),
+    },
+    Shortcut {
+        key: ShortcutKey::Character("n"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_NEXT_MISSING,
+        message: || Message::SelectNextMissing,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("g"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_PREVIEW_GUIDES,
+        message: || Message::CyclePreviewGuides,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("z"),
+        command: true,
+        shift: true,
+        description: strings::LABEL_SHORTCUT_REDO,
+        message: || Message::Redo,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("z"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_UNDO,
+        message: || Message::Undo,
+    },
+    Shortcut {
+        key: ShortcutKey::Character("y"),
+        command: true,
+        shift: false,
+        description: strings::LABEL_SHORTCUT_REDO,
+        message: || Message::Redo,
+    },
+];
+
+impl Shortcut {
+    fn matches(&self, key: &keyboard::Key<&str>, modifiers: keyboard::Modifiers) -> bool {
+        let key_matches = match (&self.key, key) {
+            (ShortcutKey::Named(expected, _), keyboard::Key::Named(named)) => expected == named,
+            (ShortcutKey::Character(expected), keyboard::Key::Character(typed)) => {
+                expected.eq_ignore_ascii_case(typed)
             }
+            _ => false,
+        };
+        key_matches && self.command == modifiers.command() && (!self.shift || modifiers.shift())
+    }
+
+    /// How the binding is written in the shortcut help, e.g. "Ctrl+Shift+Z".
+    fn keys(&self) -> String {
+        let mut keys = String::new();
+        if self.command {
+            keys.push_str(if cfg!(target_os = "macos") {
+                "Cmd+"
+            } else {
+                "Ctrl+"
+            });
         }
+        if self.shift {
+            keys.push_str("Shift+");
+        }
+        match self.key {
+            ShortcutKey::Named(_, name) => keys.push_str(name),
+            ShortcutKey::Character(character) => keys.push_str(&character.to_uppercase()),
+        }
+        keys
+    }
+}
 
-        self.index.collections = self
-            .index
-            .collections
-            .iter()
-            .filter(|x| !x.rom_indices.is_empty())
-            .cloned()
-            .collect();
+/// New box art waiting to be confirmed against the art it would overwrite, if any.
+#[derive(Debug, Clone)]
+struct PendingReplacement {
+    rom_index: usize,
+    boxart_path: PathBuf,
+    /// The art being replaced and its dimensions, or `None` when the ROM has no art yet.
+    current: Option<(image::Handle, (u32, u32))>,
+    replacement: DynamicImage,
+    /// Downscaled copy of `replacement` that adjustments are previewed on.
+    preview_base: DynamicImage,
+    replacement_preview: image::Handle,
+    adjustment: ArtAdjustment,
+    /// Set when the replacement was picked from disk, so it can be imported as-is.
+    source_file: Option<PathBuf>,
+}
+
+impl PendingReplacement {
+    fn new(
+        boxart_path: PathBuf,
+        rom_index: usize,
+        replacement: DynamicImage,
+        source_file: Option<PathBuf>,
+        adjustment: ArtAdjustment,
+        has_current: bool,
+    ) -> Result<Self, String> {
+        let current = if has_current {
+            let current = open_image(&boxart_path)?;
+            Some((image_handle(&current), (current.width(), current.height())))
+        } else {
+            None
+        };
+        let preview_base =
+            if replacement.width().max(replacement.height()) > ADJUSTMENT_PREVIEW_SIZE {
+                replacement.thumbnail(ADJUSTMENT_PREVIEW_SIZE, ADJUSTMENT_PREVIEW_SIZE)
+            } else {
+                replacement.clone()
+            };
 
-        Ok(())
+        Ok(Self {
+            rom_index,
+            boxart_path,
+            current,
+            replacement_preview: image_handle(&adjustment.apply(&preview_base)),
+            preview_base,
+            replacement,
+            adjustment,
+            source_file,
+        })
     }
 
-    fn index_collection_folder(
-        &mut self,
-        collection_direntry: DirEntry,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let collection_path = collection_direntry.path();
-        let collection_name_os = collection_direntry.file_name();
-        let collection_name = collection_name_os.to_string_lossy();
+    fn set_adjustment(&mut self, adjustment: ArtAdjustment) {
+        self.adjustment = adjustment;
+        self.replacement_preview = image_handle(&adjustment.apply(&self.preview_base));
+    }
+}
 
-        let mut collection = Collection {
-            name: collection_name.to_string(),
-            rom_indices: Vec::new(),
-        };
+/// An image that went through the clipboard this session, kept so it can be applied again later.
+#[derive(Debug, Clone)]
+struct ClipboardImage {
+    image: Arc<DynamicImage>,
+    thumbnail: image::Handle,
+}
 
-        let read_dir = std::fs::read_dir(&collection_path).map_err(|e| {
-            format!(
-                "{}{}': {}",
-                strings::ERROR_PREFIX_READ_COLLECTION,
-                collection_path.display(),
-                e
-            )
-        })?;
+impl ClipboardImage {
+    fn new(image: DynamicImage) -> Self {
+        Self {
+            thumbnail: image_handle(&image.thumbnail(
+                CLIPBOARD_HISTORY_THUMBNAIL_SIZE,
+                CLIPBOARD_HISTORY_THUMBNAIL_SIZE,
+            )),
+            image: Arc::new(image),
+        }
+    }
+}
 
-        for entry in read_dir {
-            let mut media_folder = collection_direntry.path().clone();
-            media_folder.push(".media");
-            if !media_folder.exists() {
-                std::fs::create_dir(&media_folder).map_err(|e| {
-                    format!(
-                        "{}{}': {}",
-                        strings::ERROR_PREFIX_MEDIA_DIR,
-                        media_folder.display(),
-                        e
-                    )
-                })?;
+/// Downscaled box art for the gallery, keyed by box art path. The oldest are dropped once it
+/// holds more than the configured limit.
+#[derive(Debug, Default, Clone)]
+struct ThumbnailCache {
+    handles: HashMap<PathBuf, image::Handle>,
+    /// Cached paths, oldest first.
+    order: VecDeque<PathBuf>,
+}
+
+impl ThumbnailCache {
+    fn get(&self, boxart_path: &Path) -> Option<&image::Handle> {
+        self.handles.get(boxart_path)
+    }
+
+    fn contains_key(&self, boxart_path: &Path) -> bool {
+        self.handles.contains_key(boxart_path)
+    }
+
+    fn insert(&mut self, boxart_path: PathBuf, handle: image::Handle, limit: ThumbnailCacheLimit) {
+        if self.handles.insert(boxart_path.clone(), handle).is_none() {
+            self.order.push_back(boxart_path);
+        }
+        self.evict(limit);
+    }
+
+    fn remove(&mut self, boxart_path: &Path) {
+        if self.handles.remove(boxart_path).is_some() {
+            self.order.retain(|x| x != boxart_path);
+        }
+    }
+
+    /// Drops the oldest thumbnails until no more than `limit` are left.
+    fn evict(&mut self, limit: ThumbnailCacheLimit) {
+        if limit.0 == 0 {
+            return;
+        }
+        while self.handles.len() > limit.0 {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.handles.remove(&oldest);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.handles.clear();
+        self.order.clear();
+    }
+}
+
+/// One user action that undo and redo step through.
+#[derive(Debug, Clone)]
+enum UndoStep {
+    /// Art written or deleted, for one ROM or many at once.
+    Art(Vec<ArtChange>),
+    /// Art moved by a template migration or to ROMs that lost it.
+    Renames(Vec<ArtRename>),
+    /// A collection renamed from the first name to the second.
+    CollectionRename(String, String),
+}
+
+impl UndoStep {
+    fn is_empty(&self) -> bool {
+        match self {
+            UndoStep::Art(changes) => changes.is_empty(),
+            UndoStep::Renames(renames) => renames.is_empty(),
+            UndoStep::CollectionRename(..) => false,
+        }
+    }
+
+    /// Removes the copies art changes kept, once they can no longer be undone or redone.
+    fn discard(self) {
+        if let UndoStep::Art(changes) = self {
+            for change in changes {
+                change.discard();
             }
+        }
+    }
+}
 
-            if let Ok(entry) = entry {
-                if !entry.file_type()?.is_file() {
-                    continue;
-                }
+/// The library's state plus what only the GUI keeps track of. Derefs to the library's state.
+#[derive(Debug, Clone)]
+struct State {
+    library: nextart::State,
+    /// Images written and total to write, while an image is being applied to many ROMs.
+    bulk_progress: Option<(usize, usize)>,
+    /// The whole Roms folder is being indexed again in the background.
+    reindexing: bool,
+    /// User actions that can be undone, most recent last.
+    undo_stack: Vec<UndoStep>,
+    redo_stack: Vec<UndoStep>,
+    thumbnails: ThumbnailCache,
+    /// Images pasted or copied this session, most recent first.
+    clipboard_history: VecDeque<ClipboardImage>,
+    /// The user was told writes were still running when they closed the window, so the next
+    /// close request doesn't wait for them again.
+    close_warned: bool,
+    /// Latest error and when it happened, shown over the current view until it expires.
+    toast: Option<(String, Instant)>,
+}
 
-                let mut boxart_path = media_folder.clone();
-                boxart_path.push(&format!(
-                    "{}.png",
-                    entry
-                        .path()
-                        .file_stem()
-                        .ok_or(format!("{}{:#?}", strings::ERROR_PREFIX_FILE_STEM, entry))?
-                        .display()
-                ));
+impl State {
+    fn new(library: nextart::State) -> Self {
+        Self {
+            library,
+            bulk_progress: None,
+            reindexing: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            thumbnails: ThumbnailCache::default(),
+            clipboard_history: VecDeque::new(),
+            close_warned: false,
+            toast: None,
+        }
+    }
 
-                let mut rom = Rom {
-                    name: entry
-                        .path()
-                        .file_stem()
-                        .ok_or(format!("Failed to extract file stem: {entry:#?}"))?
-                        .to_string_lossy()
-                        .into(),
-                    boxart_path: boxart_path.clone(),
-                    boxart_size: 0,
-                };
+    /// Records a user action for undo, which also drops anything that could be redone.
+    fn push_undo(&mut self, step: UndoStep) {
+        if step.is_empty() {
+            return;
+        }
+        for step in self.redo_stack.drain(..) {
+            step.discard();
+        }
+        self.undo_stack.push(step);
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0).discard();
+        }
+    }
 
-                match std::fs::exists(&boxart_path) {
-                    Ok(exists) => {
-                        if exists {
-                            if let Ok(metadata) = std::fs::metadata(&boxart_path) {
-                                rom.boxart_size = metadata.len();
-                            } else {
-                                self.errors.push(format!(
-                                    "{}{}'",
-                                    strings::ERROR_PREFIX_GET_METADATA,
-                                    boxart_path.display()
-                                ));
-                            }
+    /// Drops undo history of ROMs a merge left unlisted, whose slots a later merge hands to new ROMs.
+    fn forget_unlisted_roms(&mut self) {
+        let listed: HashSet<usize> = self.index.listed_roms().map(|(x, _)| x).collect();
+        for stack in [&mut self.undo_stack, &mut self.redo_stack] {
+            for step in stack.iter_mut() {
+                match step {
+                    UndoStep::Art(changes) => {
+                        for change in changes.extract_if(.., |x| !listed.contains(&x.rom_index)) {
+                            change.discard();
                         }
                     }
-                    Err(e) => {
-                        self.errors.push(format!(
-                            "{}{}: {}'",
-                            strings::ERROR_PREFIX_GET_METADATA,
-                            boxart_path.display(),
-                            e
-                        ));
-                    }
+                    UndoStep::Renames(renames) => renames.retain(|x| listed.contains(&x.rom_index)),
+                    UndoStep::CollectionRename(..) => {}
                 }
-
-                self.index.roms.push(rom);
-                collection.rom_indices.push(self.index.roms.len() - 1);
             }
+            stack.retain(|x| !x.is_empty());
         }
+    }
+
+    /// Adds an image to the front of the clipboard history, moving it there if it's already listed.
+    fn remember_clipboard_image(&mut self, clipboard_image: ClipboardImage) {
+        self.clipboard_history.retain(|x| {
+            x.image.color() != clipboard_image.image.color()
+                || x.image.as_bytes() != clipboard_image.image.as_bytes()
+        });
+        self.clipboard_history.push_front(clipboard_image);
+        self.clipboard_history.truncate(CLIPBOARD_HISTORY_LIMIT);
+    }
+}
 
-        self.index.collections.push(collection);
+impl std::ops::Deref for State {
+    type Target = nextart::State;
 
-        Ok(())
+    fn deref(&self) -> &nextart::State {
+        &self.library
     }
 }
 
+impl std::ops::DerefMut for State {
+    fn deref_mut(&mut self) -> &mut nextart::State {
+        &mut self.library
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ContextMenu {
+    rom_index: usize,
+    position: Point,
+}
+
 #[derive(Debug, Clone)]
 enum NextArtView {
     Setup {
         chosen_path: Option<PathBuf>,
+        config: PersistentConfig,
         error: Option<String>,
     },
     Loading {
@@ -248,6 +862,10 @@ enum NextArtView {
     },
     CollectionList {
         state: State,
+        /// Collection being renamed and the new name as typed so far.
+        renaming: Option<(String, String)>,
+        /// Pinned collection currently being dragged to a new position.
+        dragging: Option<String>,
     },
     RomList {
         state: State,
@@ -255,12 +873,86 @@ enum NextArtView {
         selected_index: Option<usize>,
         selected_image: Option<image::Handle>,
         rom_indices: Vec<usize>,
+        cursor_position: Point,
+        context_menu: Option<ContextMenu>,
+        selected_roms: BTreeSet<usize>,
+        pending_clipboard_image: Option<Box<(PathBuf, u32, u32)>>,
+        filter: RomFilter,
+        /// A write started from the ROM info panel hasn't finished yet.
+        saving: bool,
+        /// ROM whose art was opened in an external editor, watched so edits show up here.
+        external_edit: Option<usize>,
+        /// The collection is being read from disk again.
+        rescanning: bool,
+        /// ROM whose art is offered to same-named ROMs in other collections, and those ROMs.
+        matching_fill: Option<Box<(usize, Vec<usize>)>>,
+        /// While the pane divider is dragged: the cursor's x and the pane ratio when the drag
+        /// started, and the width the panes share once it has been measured.
+        resizing_panes: Option<Box<(f32, f32, Option<f32>)>>,
     },
     FatalError {
         error_description: String,
     },
     ErrorList {
         state: State,
+        category: ErrorCategory,
+        group_by_collection: bool,
+    },
+    Settings {
+        state: State,
+        /// Template text as typed, which may not be valid yet.
+        boxart_template: String,
+        /// Excluded collection patterns as typed, separated by commas.
+        excluded_collections: String,
+        /// Extension to system name pairs as typed, e.g. `sfc=SFC, gba=GBA`.
+        extension_systems: String,
+        /// System to category pairs as typed, e.g. `GBA=Handheld, MD=Console`.
+        system_categories: String,
+        /// ROMs whose art the current template would name differently, counted on entering
+        /// Settings and again whenever the template, art names or index change.
+        stale_boxart_paths: usize,
+    },
+    RecentlyModified {
+        state: State,
+    },
+    /// Decodes every box art file in the library to find broken ones.
+    ArtCheck {
+        state: State,
+        started: Instant,
+        checked: usize,
+        total: usize,
+        /// ROMs whose art failed to decode, with the error.
+        failures: Vec<(usize, String)>,
+    },
+    OrphanedArt {
+        state: State,
+        orphans: Vec<(PathBuf, u64)>,
+        /// Orphans that look like the art of a ROM that was renamed, and that ROM's index.
+        renames: Vec<(PathBuf, usize)>,
+        reclaimed: Option<u64>,
+    },
+    CompareArt {
+        /// The ROM list to return to once the replacement is confirmed or cancelled.
+        previous: Box<NextArtView>,
+        pending: PendingReplacement,
+    },
+    /// Asks before deleting the art of several ROMs at once.
+    ConfirmBulkDelete {
+        /// The ROM list to return to once the deletion is confirmed or cancelled.
+        previous: Box<NextArtView>,
+        /// Selected ROMs that have art to delete.
+        rom_indices: Vec<usize>,
+    },
+    /// Asks before leaving a ROM list whose matching fill hasn't been applied yet.
+    ConfirmDiscardMatchingFill {
+        /// The ROM list holding the matching fill, returned to if it's kept.
+        previous: Box<NextArtView>,
+        /// Where to go once the matching fill is discarded.
+        destination: Box<Message>,
+    },
+    /// Lists the keyboard shortcuts on top of another view, which keeps handling messages.
+    ShortcutHelp {
+        previous: Box<NextArtView>,
     },
 }
 
@@ -268,6 +960,7 @@ impl Default for NextArtView {
     fn default() -> Self {
         Self::Setup {
             chosen_path: None,
+            config: PersistentConfig::default(),
             error: None,
         }
     }
@@ -275,8 +968,52 @@ impl Default for NextArtView {
 
 impl NextArtView {
     pub fn view(&self) -> Element<Message> {
+        let content = self.view_content();
+        match self.state().and_then(|x| x.toast.as_ref()) {
+            Some((error, _)) => {
+                // The error list can only be opened from the lists it returns to.
+                let opens_error_list =
+                    matches!(self, Self::CollectionList { .. } | Self::RomList { .. });
+                stack![content, Self::toast(error, opens_error_list)].into()
+            }
+            None => content,
+        }
+    }
+
+    fn toast(error: &str, opens_error_list: bool) -> Element<'_, Message> {
+        container(
+            container(
+                row![text(error).width(Length::Fill)]
+                    .push_maybe(opens_error_list.then(|| {
+                        button(strings::LABEL_SHOW_ERRORS)
+                            .style(button::secondary)
+                            .on_press(Message::OpenErrorList)
+                    }))
+                    .push(
+                        button(strings::LABEL_CLOSE)
+                            .style(button::secondary)
+                            .on_press(Message::DismissToast),
+                    )
+                    .spacing(SPACING_SMALL)
+                    .align_y(Alignment::Center),
+            )
+            .max_width(TOAST_WIDTH)
+            .padding(PADDING_SMALL)
+            .style(toast_style),
+        )
+        .align_right(Length::Fill)
+        .align_bottom(Length::Fill)
+        .padding(PADDING_STANDARD)
+        .into()
+    }
+
+    fn view_content(&self) -> Element<'_, Message> {
         match self {
-            Self::Setup { chosen_path, error } => column![
+            Self::Setup {
+                chosen_path,
+                config,
+                error,
+            } => column![
                 text(strings::UI_TITLE_SETUP).font(Font {
                     weight: Weight::Bold,
                     ..Default::default()
@@ -295,7 +1032,35 @@ impl NextArtView {
                         .on_press(Message::OpenRomDirectoryPicker),
                 ]
                 .spacing(SPACING_SMALL),
+                text(strings::LABEL_ART_ROOT_HINT),
+                row![
+                    text_input(
+                        strings::LABEL_ART_ROOT,
+                        &config
+                            .art_root
+                            .as_ref()
+                            .map_or("".to_owned(), |x| x.to_string_lossy().to_string())
+                    )
+                    .width(Length::Fill),
+                    button(strings::LABEL_PICK)
+                        .padding(PADDING_BUTTON_SMALL)
+                        .on_press(Message::OpenArtRootPicker),
+                    button(strings::LABEL_CLEAR)
+                        .padding(PADDING_BUTTON_SMALL)
+                        .style(button::secondary)
+                        .on_press_maybe(
+                            config
+                                .art_root
+                                .is_some()
+                                .then_some(Message::ArtRootChosen(None))
+                        ),
+                ]
+                .spacing(SPACING_SMALL),
                 row![
+                    button(strings::LABEL_IMPORT_SETTINGS)
+                        .padding(PADDING_BUTTON)
+                        .style(button::secondary)
+                        .on_press(Message::ImportSettings),
                     Space::with_width(Length::Fill),
                     button(strings::LABEL_DONE)
                         .padding(PADDING_BUTTON)
@@ -326,31 +1091,208 @@ impl NextArtView {
             .padding(30)
             .into(),
 
-            Self::CollectionList { state } => scrollable(
+            Self::CollectionList {
+                state,
+                renaming,
+                dragging,
+            } => scrollable(
                 column![
-                    text(strings::UI_TITLE_MAIN)
-                        .font(Font {
-                            weight: Weight::Light,
-                            ..Default::default()
-                        })
-                        .size(FONT_SIZE_TITLE)
-                        .width(Length::Fill)
-                        .align_x(Alignment::Center),
-                    column(state.index.collections.iter().map(|x| {
+                    row![
+                        text(strings::UI_TITLE_MAIN)
+                            .font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                            .size(FONT_SIZE_TITLE)
+                            .width(Length::Fill)
+                            .align_x(Alignment::Center),
                         row![
+                            button(text!(
+                                "{} ({})",
+                                strings::LABEL_RECENTLY_MODIFIED,
+                                state.recently_modified.len()
+                            ))
+                            .style(button::secondary)
+                            .on_press_maybe(
+                                (!state.recently_modified.is_empty())
+                                    .then_some(Message::OpenRecentlyModified)
+                            ),
+                            button(strings::LABEL_FIND_ORPHANED_ART)
+                                .style(button::secondary)
+                                .on_press(Message::FindOrphanedArt),
+                            button(strings::LABEL_CHECK_ALL_ART)
+                                .style(button::secondary)
+                                .on_press(Message::CheckAllArt),
+                            button(if state.reindexing {
+                                strings::LABEL_REINDEXING
+                            } else {
+                                strings::LABEL_REINDEX
+                            })
+                            .style(button::secondary)
+                            .on_press_maybe((!state.reindexing).then_some(Message::Reindex)),
+                            button(strings::LABEL_UNDO)
+                                .style(button::secondary)
+                                .on_press_maybe(
+                                    (!state.undo_stack.is_empty()).then_some(Message::Undo)
+                                ),
+                            button(strings::LABEL_REDO)
+                                .style(button::secondary)
+                                .on_press_maybe(
+                                    (!state.redo_stack.is_empty()).then_some(Message::Redo)
+                                ),
+                            button(strings::LABEL_SETTINGS).on_press(Message::OpenSettings),
+                        ]
+                        .spacing(SPACING_TINY),
+                    ]
+                    .align_y(Alignment::Center),
+                    checkbox(
+                        strings::LABEL_ONLY_INCOMPLETE_COLLECTIONS,
+                        state.config.only_incomplete_collections
+                    )
+                    .on_toggle(Message::SetOnlyIncompleteCollections),
+                    Self::category_tabs(state),
+                    Self::case_duplicates_warning(state),
+                    Self::no_collections_notice(state),
+                    column(Self::visible_collections(state, renaming).map(|x| {
+                        if let Some((original, draft)) = renaming
+                            && *original == x.name
+                        {
+                            return Self::rename_collection_row(
+                                original,
+                                draft,
+                                state.collection_rename_error(original, draft),
+                            );
+                        }
+
+                        let art_exempt = state.is_art_exempt(x);
+                        let text_style = move |theme: &iced::Theme| text::Style {
+                            color: art_exempt.then(|| theme.palette().text.scale_alpha(0.5)),
+                        };
+
+                        let pinned = state.config.pinned_collections.contains(&x.name);
+                        let collection_row = row![
                             button(strings::LABEL_OPEN).on_press(Message::OpenRomList(
                                 x.name.clone(),
                                 x.rom_indices.clone()
                             )),
                             column![
-                                text(x.name.clone()).font(Font {
-                                    weight: Weight::Bold,
-                                    ..Default::default()
-                                }),
-                                text!("{} {}", x.rom_indices.len(), strings::LABEL_ROMS)
-                            ],
+                                name_text(&x.name, COLLECTION_NAME_MAX_CHARS, |x| x
+                                    .font(Font {
+                                        weight: Weight::Bold,
+                                        ..Default::default()
+                                    })
+                                    .style(text_style)),
+                                if art_exempt {
+                                    text!(
+                                        "{} {}, {}",
+                                        x.rom_indices.len(),
+                                        strings::LABEL_ROMS,
+                                        strings::LABEL_ART_NOT_EXPECTED
+                                    )
+                                } else {
+                                    text!(
+                                        "{} {}, {} {}",
+                                        x.rom_indices.len(),
+                                        strings::LABEL_ROMS,
+                                        x.missing_art_count(&state.index.roms),
+                                        strings::LABEL_MISSING_BOX_ART
+                                    )
+                                }
+                                .style(text_style)
+                            ]
+                            .push_maybe(
+                                state
+                                    .previous_coverage
+                                    .as_ref()
+                                    .and_then(|snapshot| snapshot.art_added(x, &state.index.roms))
+                                    .filter(|added| *added != 0)
+                                    .map(|added| if added > 0 {
+                                        text!("+{} {}", added, strings::LABEL_ART_ADDED_SINCE_LAST)
+                                            .style(text::success)
+                                    } else {
+                                        text!("{} {}", -added, strings::LABEL_ART_LOST_SINCE_LAST)
+                                            .style(text::danger)
+                                    })
+                            )
+                            .width(Length::Fill),
+                            if let Some(count) = state.collection_errors.get(&x.name) {
+                                Element::from(
+                                    button(text!("{} {}", count, strings::LABEL_INDEXING_ERRORS))
+                                        .style(danger_button_style)
+                                        .on_press(Message::OpenErrorList),
+                                )
+                            } else {
+                                Space::with_width(Length::Shrink).into()
+                            },
+                            pick_list(
+                                state
+                                    .config
+                                    .categories()
+                                    .into_iter()
+                                    .map(str::to_owned)
+                                    .collect::<Vec<_>>(),
+                                state.config.collection_category(&x.name).map(str::to_owned),
+                                {
+                                    let collection_name = x.name.clone();
+                                    move |category| {
+                                        Message::SetCollectionCategory(
+                                            collection_name.clone(),
+                                            category,
+                                        )
+                                    }
+                                }
+                            )
+                            .placeholder(strings::LABEL_CATEGORY_PLACEHOLDER),
+                            button(if art_exempt {
+                                strings::LABEL_EXPECT_ART
+                            } else {
+                                strings::LABEL_IGNORE_MISSING
+                            })
+                            .style(button::secondary)
+                            // The marker file decides for these, not the setting.
+                            .on_press_maybe(
+                                (!x.no_media).then(|| Message::ToggleArtExempt(x.name.clone()))
+                            ),
+                            button(strings::LABEL_COPY_SUMMARY)
+                                .style(button::secondary)
+                                .on_press(Message::SetClipboardText(
+                                    x.summary(&state.index.roms, state.config.size_units)
+                                )),
+                            button(if pinned {
+                                strings::LABEL_UNPIN
+                            } else {
+                                strings::LABEL_PIN
+                            })
+                            .style(button::secondary)
+                            .on_press(Message::TogglePinnedCollection(x.name.clone())),
+                            write_button(
+                                button(strings::LABEL_RENAME).style(button::secondary),
+                                (!state.single_collection)
+                                    .then(|| Message::StartRenamingCollection(x.name.clone())),
+                                state.config.is_read_only()
+                            ),
                         ]
                         .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center);
+
+                        if !pinned {
+                            return collection_row.into();
+                        }
+                        let is_dragged = dragging.as_ref() == Some(&x.name);
+                        mouse_area(container(collection_row).style(move |theme| {
+                            if is_dragged {
+                                container::rounded_box(theme)
+                            } else {
+                                container::Style::default()
+                            }
+                        }))
+                        .on_press(Message::StartDraggingCollection(x.name.clone()))
+                        .on_enter(Message::DragCollectionOver(x.name.clone()))
+                        .interaction(if dragging.is_some() {
+                            mouse::Interaction::Grabbing
+                        } else {
+                            mouse::Interaction::Grab
+                        })
                         .into()
                     }))
                     .spacing(SPACING_STANDARD)
@@ -379,8 +1321,21 @@ impl NextArtView {
                                 ..Default::default()
                             })
                             .into()
+                    },
+                    if state.skipped_collections.is_empty() {
+                        Element::from(Space::with_height(0))
+                    } else {
+                        button(text!(
+                            "{} ({})",
+                            strings::LABEL_SHOW_SKIPPED,
+                            state.skipped_collections.len()
+                        ))
+                        .style(button::secondary)
+                        .on_press(Message::OpenErrorList)
+                        .into()
                     }
                 ]
+                .spacing(SPACING_SMALL)
                 .padding(30),
             )
             .into(),
@@ -391,69 +1346,193 @@ impl NextArtView {
                 selected_index,
                 selected_image,
                 rom_indices,
+                context_menu,
+                selected_roms,
+                pending_clipboard_image,
+                filter,
+                saving,
+                rescanning,
+                matching_fill,
+                ..
             } => {
-                let mut rom_indice_tuples: Vec<(usize, &Rom)> = rom_indices
+                let rom_indice_tuples = Self::visible_roms(state, title, rom_indices, filter);
+                let list_portion = (state.config.pane_ratio * f32::from(PANE_PORTIONS)) as u16;
+                // Bulk actions only touch what the filter leaves visible.
+                let filtered = rom_indice_tuples.len() != rom_indices.len();
+
+                let missing_names: Vec<&str> = rom_indice_tuples
                     .iter()
-                    .filter_map(|rom_index| {
-                        if let Some(rom) = state.index.roms.get(*rom_index) {
-                            Some((*rom_index, rom))
-                        } else {
-                            None
-                        }
-                    })
+                    .filter(|(_, rom)| rom.boxart_size == 0)
+                    .map(|(_, rom)| rom.name.as_str())
                     .collect();
-                rom_indice_tuples.sort_by_key(|x| &x.1.name);
 
-                column![
+                let content = column![
                     row![
-                        button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
-                        text(title)
-                            .font(Font {
+                        button(strings::LABEL_COLLECTIONS)
+                            .padding(0)
+                            .style(button::text)
+                            .on_press(Message::OpenCollectionList),
+                        text("/").font(Font {
+                            weight: Weight::Light,
+                            ..Default::default()
+                        }),
+                        text(title),
+                    ]
+                    .spacing(SPACING_TINY),
+                    row![
+                        button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
+                        container(name_text(title, COLLECTION_NAME_MAX_CHARS, |x| x
+                            .font(Font {
                                 weight: Weight::Light,
                                 ..Default::default()
                             })
-                            .size(32)
-                            .width(Length::Fill)
-                            .align_x(Alignment::Center)
-                    ],
+                            .size(FONT_SIZE_TITLE)))
+                        .center_x(Length::Fill),
+                        row![
+                            button(if *rescanning {
+                                strings::LABEL_RESCANNING
+                            } else {
+                                strings::LABEL_RESCAN_COLLECTION
+                            })
+                            .style(button::secondary)
+                            .on_press_maybe((!*rescanning).then_some(Message::RescanCollection)),
+                            button(strings::LABEL_UNDO)
+                                .style(button::secondary)
+                                .on_press_maybe(
+                                    (!state.undo_stack.is_empty()).then_some(Message::Undo)
+                                ),
+                            button(strings::LABEL_REDO)
+                                .style(button::secondary)
+                                .on_press_maybe(
+                                    (!state.redo_stack.is_empty()).then_some(Message::Redo)
+                                ),
+                            button(strings::LABEL_NEXT_MISSING).on_press_maybe(
+                                (!missing_names.is_empty()).then_some(Message::SelectNextMissing)
+                            ),
+                            pick_list(
+                                VerifiedFilter::ALL,
+                                Some(filter.verified),
+                                Message::SetVerifiedFilter
+                            ),
+                            pick_list(
+                                RomLayout::ALL,
+                                Some(state.config.rom_layout),
+                                Message::SetRomLayout
+                            ),
+                            button(strings::LABEL_COPY_MISSING_NAMES)
+                                .style(button::secondary)
+                                .on_press_maybe((!missing_names.is_empty()).then(|| {
+                                    Message::SetClipboardText(missing_names.join("\n"))
+                                })),
+                        ]
+                        .spacing(SPACING_TINY),
+                    ]
+                    .align_y(Alignment::Center),
                     row![
-                        scrollable(
-                            column(rom_indice_tuples.iter().map(|(index, rom)| {
-                                row![
-                                    button(strings::LABEL_MANAGE)
-                                        .on_press(Message::SelectRom(*index)),
-                                    column![
-                                        text(rom.name.clone()).font(Font {
-                                            weight: Weight::Bold,
-                                            ..Default::default()
-                                        }),
-                                        if rom.boxart_size == 0 {
-                                            text(strings::LABEL_NO_BOX_ART)
-                                        } else {
-                                            text!(
-                                                "{} {}",
-                                                ByteSizeFormatter::format_auto(
-                                                    rom.boxart_size,
-                                                    bittenhumans::consts::System::Binary
-                                                ),
-                                                strings::LABEL_BOX_ART
-                                            )
-                                        }
-                                    ],
-                                ]
-                                .spacing(SPACING_SMALL)
-                                .into()
-                            }),)
-                            .spacing(SPACING_STANDARD)
-                            .padding(PADDING_STANDARD),
+                        text_input(strings::LABEL_SEARCH, &filter.search)
+                            .id(ROM_SEARCH_INPUT_ID)
+                            .on_input(Message::SetRomSearch)
+                            .width(Length::Fill),
+                    ]
+                    .push_maybe(filtered.then(|| {
+                        text!(
+                            "{} {} {} {}",
+                            strings::LABEL_SHOWING,
+                            rom_indice_tuples.len(),
+                            strings::LABEL_OF,
+                            rom_indices.len()
+                        )
+                    }))
+                    .push(
+                        button(text!(
+                            "{} ({})",
+                            strings::LABEL_SELECT_ALL_IN_VIEW,
+                            rom_indice_tuples.len()
+                        ))
+                        .style(button::secondary)
+                        .on_press_maybe(
+                            (!rom_indice_tuples.is_empty()).then(|| {
+                                Message::SelectRoms(
+                                    rom_indice_tuples.iter().map(|(index, _)| *index).collect(),
+                                )
+                            })
                         ),
-                        if let Some(selected_index) = selected_index {
+                    )
+                    .spacing(SPACING_SMALL)
+                    .align_y(Alignment::Center),
+                    Self::default_art_bar(
+                        title,
+                        state.config.collection_default_art.get(title),
+                        rom_indice_tuples
+                            .iter()
+                            .filter(|(_, rom)| rom.boxart_size == 0)
+                            .map(|(index, _)| *index)
+                            .collect(),
+                        filtered,
+                        state.config.is_read_only(),
+                    ),
+                    Self::clipboard_confirmation(pending_clipboard_image.as_deref()),
+                    Self::matching_fill_confirmation(state, matching_fill.as_deref()),
+                    Self::selection_bar(
+                        selected_roms,
+                        &rom_indice_tuples,
+                        state.config.art_source().is_some(),
+                        state.config.is_read_only()
+                    ),
+                    Self::bulk_progress_bar(state.bulk_progress),
+                    row![
+                        container(match state.config.rom_layout {
+                            RomLayout::List => scrollable(
+                                column(rom_indice_tuples.iter().map(|(index, rom)| {
+                                    Self::rom_row(
+                                        rom,
+                                        *index,
+                                        selected_roms.contains(index),
+                                        state.config.is_verified(title, &rom.name),
+                                        state.config.density,
+                                        state.config.size_units,
+                                    )
+                                }))
+                                .spacing(row_spacing(state.config.density))
+                                .padding(list_padding(state.config.density)),
+                            ),
+                            RomLayout::Gallery => scrollable(
+                                row(rom_indice_tuples.iter().map(|(index, rom)| {
+                                    Self::gallery_tile(
+                                        rom,
+                                        *index,
+                                        state.thumbnails.get(&rom.boxart_path),
+                                        *selected_index == Some(*index),
+                                        state.config.is_verified(title, &rom.name),
+                                    )
+                                }))
+                                .spacing(row_spacing(state.config.density))
+                                .padding(list_padding(state.config.density))
+                                .wrap(),
+                            )
+                            .width(Length::Fill),
+                        })
+                        .width(Length::FillPortion(list_portion)),
+                        mouse_area(
+                            container(vertical_rule(1))
+                                .center_x(PANE_DIVIDER_WIDTH)
+                                .height(Length::Fill)
+                        )
+                        .interaction(mouse::Interaction::ResizingHorizontally)
+                        .on_press(Message::StartResizingPanes),
+                        container(if let Some(selected_index) = selected_index {
                             Self::rom_info_column(
                                 state.index.roms.get(*selected_index).expect(
                                     "This should not be reachable! selected_index did not exist!",
                                 ),
                                 *selected_index,
                                 selected_image,
+                                &state.config,
+                                state
+                                    .config
+                                    .is_verified(title, &state.index.roms[*selected_index].name),
+                                *saving,
+                                &state.clipboard_history,
                             )
                         } else {
                             column![
@@ -462,71 +1541,972 @@ impl NextArtView {
                                     .align_x(Horizontal::Center)
                             ]
                             .into()
-                        }
+                        })
+                        .width(Length::FillPortion(PANE_PORTIONS - list_portion)),
                     ]
                     .padding(PADDING_SMALL)
                 ]
                 .spacing(20)
-                .padding(30)
-                .into()
+                .padding(30);
+
+                if let Some(menu) = context_menu {
+                    stack![
+                        content,
+                        mouse_area(Space::new(Length::Fill, Length::Fill))
+                            .on_press(Message::CloseContextMenu)
+                            .on_right_press(Message::CloseContextMenu),
+                        Self::context_menu(
+                            state.index.roms.get(menu.rom_index).expect(
+                                "This should not be reachable! context menu rom_index did not exist!",
+                            ),
+                            menu,
+                            state.config.is_verified(
+                                title,
+                                &state.index.roms[menu.rom_index].name
+                            ),
+                            state.config.is_read_only(),
+                        )
+                    ]
+                    .into()
+                } else {
+                    content.into()
+                }
             }
 
-            Self::ErrorList { state } => column![
+            Self::ErrorList {
+                state,
+                category,
+                group_by_collection,
+            } => column![
                 row![
                     button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
                     text(strings::UI_TITLE_ERRORS)
-                        .size(32)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
+                    checkbox(strings::LABEL_GROUP_BY_COLLECTION, *group_by_collection)
+                        .on_toggle(Message::SetGroupErrorsByCollection),
+                    pick_list(
+                        ErrorCategory::ALL,
+                        Some(*category),
+                        Message::SetErrorCategory
+                    ),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center),
+                scrollable(
+                    column![
+                        Self::error_rows(state, *category, *group_by_collection),
+                        if state.skipped_collections.is_empty() {
+                            column![]
+                        } else {
+                            column![
+                                text(strings::UI_TITLE_SKIPPED).size(FONT_SIZE_SUBTITLE),
+                                text(strings::LABEL_SKIPPED_EXPLANATION).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                }),
+                                column(
+                                    state
+                                        .skipped_collections
+                                        .iter()
+                                        .map(|x| text(x.display().to_string()).into())
+                                )
+                            ]
+                            .spacing(SPACING_SMALL)
+                        }
+                    ]
+                    .spacing(SPACING_STANDARD)
+                )
+            ]
+            .spacing(20)
+            .padding(30)
+            .into(),
+
+            Self::Settings {
+                state,
+                boxart_template,
+                excluded_collections,
+                extension_systems,
+                system_categories,
+                stale_boxart_paths,
+            } => column![
+                row![
+                    button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
+                    text(strings::UI_TITLE_SETTINGS)
+                        .size(FONT_SIZE_TITLE)
                         .width(Length::Fill)
                         .align_x(Alignment::Center)
                 ]
                 .spacing(10),
-                scrollable(column(state.errors.iter().map(|x| {
-                    row![
-                        button(strings::LABEL_COPY).on_press(Message::SetClipboardText(x.clone())),
-                        text(x)
+                scrollable(
+                    column![
+                        row![
+                            text(strings::LABEL_SIZE_UNITS).width(Length::Fill),
+                            pick_list(
+                                SizeUnits::ALL,
+                                Some(state.config.size_units),
+                                Message::SetSizeUnits
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_THEME).width(Length::Fill),
+                            pick_list(AppTheme::ALL, Some(state.config.theme), Message::SetTheme),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![text(strings::LABEL_UI_SCALE).width(Length::Fill)]
+                            .extend(UiScale::ALL.into_iter().map(|ui_scale| {
+                                button(text(ui_scale.to_string()))
+                                    .style(if ui_scale == state.config.ui_scale {
+                                        button::primary
+                                    } else {
+                                        button::secondary
+                                    })
+                                    .on_press(Message::SetUiScale(ui_scale))
+                                    .into()
+                            }))
+                            .spacing(SPACING_TINY)
+                            .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_DENSITY).width(Length::Fill),
+                            pick_list(
+                                ListDensity::ALL,
+                                Some(state.config.density),
+                                Message::SetDensity
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_ART_TRANSPARENCY).width(Length::Fill),
+                            pick_list(
+                                ArtTransparency::ALL,
+                                Some(state.config.art_transparency),
+                                Message::SetArtTransparency
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            column![
+                                text(strings::LABEL_BOXART_TEMPLATE),
+                                text(
+                                    if PersistentConfig::is_valid_boxart_template(boxart_template) {
+                                        strings::LABEL_BOXART_TEMPLATE_HINT
+                                    } else {
+                                        strings::ERROR_INVALID_BOXART_TEMPLATE
+                                    }
+                                )
+                                .font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            text_input(DEFAULT_BOXART_TEMPLATE, boxart_template)
+                                .on_input(Message::SetBoxartTemplate)
+                                .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        Self::template_migration_row(state, *stale_boxart_paths),
+                        row![
+                            column![
+                                text(strings::LABEL_EXCLUDED_COLLECTIONS),
+                                text(strings::LABEL_EXCLUDED_COLLECTIONS_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            text_input(
+                                strings::LABEL_EXCLUDED_COLLECTIONS_PLACEHOLDER,
+                                excluded_collections
+                            )
+                            .on_input(Message::SetExcludedCollections)
+                            .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        column![
+                            checkbox(
+                                strings::LABEL_MERGE_CASE_DUPLICATES_SETTING,
+                                state.config.merge_case_duplicates
+                            )
+                            .on_toggle(Message::SetMergeCaseDuplicates),
+                            text(strings::LABEL_MERGE_CASE_DUPLICATES_HINT).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                        ],
+                        column![
+                            checkbox(
+                                strings::LABEL_KEEP_PREVIOUS_ART,
+                                state.config.keep_previous_art
+                            )
+                            .on_toggle(Message::SetKeepPreviousArt),
+                            text(strings::LABEL_KEEP_PREVIOUS_ART_HINT).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                        ],
+                        column![
+                            checkbox(
+                                strings::LABEL_READ_ART_DIMENSIONS,
+                                state.config.read_art_dimensions
+                            )
+                            .on_toggle(Message::SetReadArtDimensions),
+                            text(strings::LABEL_READ_ART_DIMENSIONS_HINT).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                        ],
+                        column![
+                            checkbox(strings::LABEL_READ_GAMELISTS, state.config.read_gamelists)
+                                .on_toggle(Message::SetReadGamelists),
+                            text(strings::LABEL_READ_GAMELISTS_HINT).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                        ],
+                        row![
+                            column![
+                                checkbox(
+                                    strings::LABEL_GROUP_BY_EXTENSION,
+                                    state.config.group_by_extension
+                                )
+                                .on_toggle(Message::SetGroupByExtension),
+                                text(strings::LABEL_GROUP_BY_EXTENSION_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            text_input(
+                                strings::LABEL_EXTENSION_SYSTEMS_PLACEHOLDER,
+                                extension_systems
+                            )
+                            .on_input_maybe(
+                                state
+                                    .config
+                                    .group_by_extension
+                                    .then_some(Message::SetExtensionSystems)
+                            )
+                            .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            column![
+                                text(strings::LABEL_SYSTEM_CATEGORIES),
+                                text(strings::LABEL_SYSTEM_CATEGORIES_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            text_input(
+                                strings::LABEL_SYSTEM_CATEGORIES_PLACEHOLDER,
+                                system_categories
+                            )
+                            .on_input(Message::SetSystemCategories)
+                            .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            column![
+                                text(strings::LABEL_EDITOR_COMMAND),
+                                text(strings::LABEL_EDITOR_COMMAND_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            text_input(
+                                strings::LABEL_EDITOR_COMMAND_PLACEHOLDER,
+                                &state.config.editor_command
+                            )
+                            .on_input(Message::SetEditorCommand)
+                            .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            column![
+                                text(strings::LABEL_ART_SOURCE),
+                                text(strings::LABEL_ART_SOURCE_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            pick_list(
+                                ArtSourceKind::ALL,
+                                Some(state.config.art_source),
+                                Message::SetArtSource
+                            ),
+                            text_input(
+                                match state.config.art_source {
+                                    ArtSourceKind::Http =>
+                                        strings::LABEL_ART_SOURCE_URL_PLACEHOLDER,
+                                    _ => strings::LABEL_ART_SOURCE_FOLDER_PLACEHOLDER,
+                                },
+                                &state.config.art_source_location
+                            )
+                            .on_input_maybe(
+                                (state.config.art_source != ArtSourceKind::None)
+                                    .then_some(Message::SetArtSourceLocation)
+                            )
+                            .width(Length::FillPortion(1)),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_PREVIEW_MAX_SIZE).width(Length::Fill),
+                            pick_list(
+                                PreviewSize::ALL,
+                                Some(state.config.preview_max_size),
+                                Message::SetPreviewMaxSize
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_PREVIEW_GUIDES).width(Length::Fill),
+                            pick_list(
+                                PreviewGuides::ALL,
+                                Some(state.config.preview_guides),
+                                Message::SetPreviewGuides
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_THUMBNAIL_CACHE_LIMIT).width(Length::Fill),
+                            pick_list(
+                                ThumbnailCacheLimit::ALL,
+                                Some(state.config.thumbnail_cache_limit),
+                                Message::SetThumbnailCacheLimit
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            column![
+                                checkbox(
+                                    strings::LABEL_PERSIST_THUMBNAILS,
+                                    state.config.persist_thumbnails
+                                )
+                                .on_toggle(Message::SetPersistThumbnails),
+                                text(strings::LABEL_PERSIST_THUMBNAILS_HINT).font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                })
+                            ]
+                            .width(Length::Fill),
+                            write_button(
+                                button(strings::LABEL_CLEAR_THUMBNAIL_CACHE)
+                                    .style(button::secondary),
+                                Some(Message::ClearThumbnailCache),
+                                state.config.is_read_only()
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_DEFAULT_ROM_ACTION).width(Length::Fill),
+                            pick_list(
+                                DefaultRomAction::ALL,
+                                Some(state.config.default_rom_action),
+                                Message::SetDefaultRomAction
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_NAME_NORMALIZATION).width(Length::Fill),
+                            pick_list(
+                                NameNormalization::ALL,
+                                Some(state.config.name_normalization),
+                                Message::SetNameNormalization
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_MEDIA_FOLDERS).width(Length::Fill),
+                            pick_list(
+                                MediaFolderCreation::ALL,
+                                Some(state.config.media_folder_creation),
+                                Message::SetMediaFolderCreation
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_FS_TIMEOUT).width(Length::Fill),
+                            pick_list(
+                                FsTimeout::ALL,
+                                Some(state.config.fs_timeout),
+                                Message::SetFsTimeout
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_READ_BUFFER_SIZE).width(Length::Fill),
+                            pick_list(
+                                ReadBufferSize::ALL,
+                                Some(state.config.read_buffer_size),
+                                Message::SetReadBufferSize
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_CLIPBOARD_ALPHA).width(Length::Fill),
+                            pick_list(
+                                ClipboardAlpha::ALL,
+                                Some(state.config.clipboard_alpha),
+                                Message::SetClipboardAlpha
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_CLIPBOARD_WARN).width(Length::Fill),
+                            pick_list(
+                                Megapixels::ALL,
+                                Some(state.config.clipboard_warn_megapixels),
+                                Message::SetClipboardWarnMegapixels
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_IMPORT_RESIZE).width(Length::Fill),
+                            pick_list(
+                                ImportResize::ALL,
+                                Some(state.config.import_resize),
+                                Message::SetImportResize
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_RESIZE_FILTER).width(Length::Fill),
+                            pick_list(
+                                ResizeFilter::ALL,
+                                Some(state.config.resize_filter),
+                                Message::SetResizeFilter
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_COLOR_TYPE).width(Length::Fill),
+                            pick_list(
+                                PngColorType::ALL,
+                                Some(state.config.png_color_type),
+                                Message::SetPngColorType
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        row![
+                            text(strings::LABEL_EXPORT_SETTINGS_HINT).width(Length::Fill),
+                            write_button(
+                                button(strings::LABEL_EXPORT_SETTINGS).style(button::secondary),
+                                Some(Message::ExportSettings),
+                                state.config.is_read_only()
+                            ),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center),
+                        column![
+                            checkbox(strings::LABEL_READ_ONLY, state.config.is_read_only())
+                                .on_toggle_maybe(
+                                    (!READ_ONLY_LAUNCH.load(Ordering::Relaxed))
+                                        .then_some(Message::SetReadOnly)
+                                ),
+                            text(strings::LABEL_READ_ONLY_HINT).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            })
+                        ],
                     ]
-                    .spacing(10)
-                    .into()
-                })))
+                    .spacing(SPACING_STANDARD)
+                    .padding(PADDING_SMALL)
+                )
             ]
             .spacing(20)
             .padding(30)
             .into(),
 
-            Self::FatalError { error_description } => column![
-                text(strings::UI_TITLE_ERROR).font(Font {
-                    weight: Weight::Bold,
-                    ..Default::default()
-                }),
-                text(error_description).style(|theme: &iced::Theme| text::Style {
-                    color: Some(theme.palette().text.scale_alpha(0.5))
-                }),
+            Self::RecentlyModified { state } => column![
                 row![
-                    button(strings::LABEL_RESTART).on_press(Message::ResetState),
-                    Space::with_width(Length::Fill),
-                    button(strings::LABEL_COPY)
-                        .on_press(Message::SetClipboardText(error_description.clone()))
+                    button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
+                    text(strings::UI_TITLE_RECENTLY_MODIFIED)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center)
                 ]
+                .spacing(10),
+                scrollable(
+                    column(state.recently_modified.iter().rev().map(|rom_index| {
+                        let rom = &state.index.roms[*rom_index];
+                        row![
+                            button(strings::LABEL_OPEN)
+                                .on_press(Message::OpenRecentRom(*rom_index)),
+                            column![
+                                text(&rom.display_name),
+                                text(
+                                    state
+                                        .collection_of(*rom_index)
+                                        .map_or("", |x| x.name.as_str())
+                                )
+                                .font(Font {
+                                    weight: Weight::Light,
+                                    ..Default::default()
+                                }),
+                            ]
+                            .width(Length::Fill),
+                            if rom.boxart_size == 0 {
+                                text(strings::LABEL_DELETED)
+                            } else {
+                                text(ByteSizeFormatter::format_auto(
+                                    rom.boxart_size,
+                                    state.config.size_units.system(),
+                                ))
+                            },
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .align_y(Alignment::Center)
+                        .into()
+                    }))
+                    .spacing(SPACING_TINY)
+                )
             ]
             .spacing(20)
             .padding(30)
             .into(),
-        }
+
+            Self::ArtCheck {
+                state,
+                checked,
+                total,
+                failures,
+                ..
+            } => column![
+                row![
+                    button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
+                    text(strings::UI_TITLE_ART_CHECK)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center)
+                ]
+                .spacing(10),
+                if checked < total {
+                    row![
+                        text!("{} {}/{}", strings::LABEL_CHECKING_ART, checked, total),
+                        progress_bar(0.0..=*total as f32, *checked as f32)
+                            .height(Length::Fixed(8.0)),
+                    ]
+                    .spacing(SPACING_SMALL)
+                    .align_y(Alignment::Center)
+                } else {
+                    row![
+                        text!(
+                            "{} {}, {} {}",
+                            total,
+                            strings::LABEL_ART_FILES_CHECKED,
+                            failures.len(),
+                            strings::LABEL_ART_FILES_BROKEN
+                        )
+                        .width(Length::Fill),
+                        button(strings::LABEL_COPY_REPORT)
+                            .style(button::secondary)
+                            .on_press_maybe((!failures.is_empty()).then(|| {
+                                Message::SetClipboardText(
+                                    failures
+                                        .iter()
+                                        .map(|(_, error)| error.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join("\n"),
+                                )
+                            })),
+                    ]
+                    .spacing(SPACING_SMALL)
+                    .align_y(Alignment::Center)
+                },
+                text(strings::LABEL_ART_CHECK_EXPLANATION).font(Font {
+                    weight: Weight::Light,
+                    ..Default::default()
+                }),
+                scrollable(
+                    column(failures.iter().map(|(rom_index, error)| {
+                        column![
+                            text!(
+                                "{} / {}",
+                                state
+                                    .collection_of(*rom_index)
+                                    .map_or("", |x| x.name.as_str()),
+                                state.index.roms[*rom_index].display_name
+                            ),
+                            text(error).font(Font {
+                                weight: Weight::Light,
+                                ..Default::default()
+                            }),
+                        ]
+                        .into()
+                    }))
+                    .spacing(SPACING_SMALL)
+                )
+            ]
+            .spacing(20)
+            .padding(30)
+            .into(),
+
+            Self::OrphanedArt {
+                state,
+                orphans,
+                renames,
+                reclaimed,
+            } => column![
+                row![
+                    button(strings::LABEL_BACK).on_press(Message::OpenCollectionList),
+                    text(strings::UI_TITLE_ORPHANED_ART)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center)
+                ]
+                .spacing(10),
+                row![
+                    text!(
+                        "{} {}, {}",
+                        orphans.len(),
+                        strings::LABEL_ORPHANED_FILES,
+                        ByteSizeFormatter::format_auto(
+                            orphans.iter().map(|(_, size)| size).sum(),
+                            state.config.size_units.system()
+                        )
+                    )
+                    .width(Length::Fill),
+                    write_button(
+                        button(strings::LABEL_DELETE_ALL).style(danger_button_style),
+                        (!orphans.is_empty()).then_some(Message::DeleteOrphanedArt),
+                        state.config.is_read_only()
+                    ),
+                ]
+                .align_y(Alignment::Center),
+                if let Some(reclaimed) = reclaimed {
+                    text!(
+                        "{} {}",
+                        strings::LABEL_RECLAIMED,
+                        ByteSizeFormatter::format_auto(
+                            *reclaimed,
+                            state.config.size_units.system()
+                        )
+                    )
+                } else {
+                    text(strings::LABEL_ORPHANED_EXPLANATION)
+                }
+                .font(Font {
+                    weight: Weight::Light,
+                    ..Default::default()
+                }),
+                Self::orphan_renames(state, renames),
+                scrollable(
+                    column(orphans.iter().map(|(path, size)| {
+                        row![
+                            text(path.display().to_string()).width(Length::Fill),
+                            text(ByteSizeFormatter::format_auto(
+                                *size,
+                                state.config.size_units.system()
+                            )),
+                        ]
+                        .spacing(SPACING_SMALL)
+                        .into()
+                    }))
+                    .spacing(SPACING_TINY)
+                )
+            ]
+            .spacing(20)
+            .padding(30)
+            .into(),
+
+            Self::CompareArt { pending, .. } => {
+                let replacement = |label| {
+                    Self::compare_pane(
+                        label,
+                        &pending.replacement_preview,
+                        (pending.replacement.width(), pending.replacement.height()),
+                    )
+                };
+                let (title, panes, confirm) = match &pending.current {
+                    Some((current, dimensions)) => (
+                        strings::UI_TITLE_COMPARE_ART,
+                        row![
+                            Self::compare_pane(strings::LABEL_CURRENT_ART, current, *dimensions),
+                            replacement(strings::LABEL_REPLACEMENT_ART),
+                        ],
+                        strings::LABEL_REPLACE,
+                    ),
+                    None => (
+                        strings::UI_TITLE_NEW_ART,
+                        row![replacement(strings::LABEL_NEW_ART)],
+                        strings::LABEL_SAVE_ART,
+                    ),
+                };
+                column![
+                    text(title)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
+                    panes.spacing(SPACING_STANDARD).height(Length::Fill),
+                    Self::adjustment_sliders(pending.adjustment),
+                    row![
+                        Space::with_width(Length::Fill),
+                        button(strings::LABEL_CANCEL)
+                            .style(button::secondary)
+                            .on_press(Message::CancelReplacement),
+                        button(confirm).on_press(Message::ConfirmReplacement),
+                    ]
+                    .spacing(SPACING_TINY),
+                ]
+                .spacing(20)
+                .padding(30)
+                .into()
+            }
+
+            Self::ConfirmBulkDelete {
+                previous,
+                rom_indices,
+            } => {
+                let Some(state) = previous.state() else {
+                    return Space::with_height(0).into();
+                };
+                let roms: Vec<&Rom> = rom_indices
+                    .iter()
+                    .filter_map(|x| state.index.roms.get(*x))
+                    .collect();
+                column![
+                    text(strings::UI_TITLE_BULK_DELETE)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
+                    text!(
+                        "{} {} {}, {}",
+                        strings::LABEL_PREFIX_BULK_DELETE,
+                        roms.len(),
+                        strings::LABEL_SUFFIX_BULK_DELETE,
+                        ByteSizeFormatter::format_auto(
+                            roms.iter().map(|x| x.boxart_size).sum(),
+                            state.config.size_units.system()
+                        )
+                    ),
+                    scrollable(
+                        column(roms.iter().map(|rom| {
+                            row![
+                                text(&rom.display_name).width(Length::Fill),
+                                text(ByteSizeFormatter::format_auto(
+                                    rom.boxart_size,
+                                    state.config.size_units.system()
+                                )),
+                            ]
+                            .spacing(SPACING_SMALL)
+                            .into()
+                        }))
+                        .spacing(SPACING_TINY)
+                    )
+                    .height(Length::Fill),
+                    row![
+                        Space::with_width(Length::Fill),
+                        button(strings::LABEL_CANCEL)
+                            .style(button::secondary)
+                            .on_press(Message::CancelBulkDelete),
+                        write_button(
+                            button(strings::LABEL_DELETE).style(danger_button_style),
+                            (!roms.is_empty()).then_some(Message::ConfirmBulkDelete),
+                            state.config.is_read_only()
+                        ),
+                    ]
+                    .spacing(SPACING_TINY),
+                ]
+                .spacing(20)
+                .padding(30)
+                .into()
+            }
+
+            Self::ConfirmDiscardMatchingFill { previous, .. } => {
+                let targets = match previous.as_ref() {
+                    Self::RomList {
+                        matching_fill: Some(matching_fill),
+                        ..
+                    } => matching_fill.1.len(),
+                    _ => 0,
+                };
+                column![
+                    text(strings::UI_TITLE_DISCARD_MATCHING_FILL)
+                        .size(FONT_SIZE_TITLE)
+                        .width(Length::Fill)
+                        .align_x(Alignment::Center),
+                    text!(
+                        "{} {} {}",
+                        strings::LABEL_PREFIX_DISCARD_MATCHING_FILL,
+                        targets,
+                        strings::LABEL_SUFFIX_DISCARD_MATCHING_FILL
+                    ),
+                    Space::with_height(Length::Fill),
+                    row![
+                        Space::with_width(Length::Fill),
+                        button(strings::LABEL_KEEP_REVIEWING)
+                            .style(button::secondary)
+                            .on_press(Message::KeepMatchingFill),
+                        button(strings::LABEL_DISCARD)
+                            .style(danger_button_style)
+                            .on_press(Message::DiscardMatchingFill),
+                    ]
+                    .spacing(SPACING_TINY),
+                ]
+                .spacing(20)
+                .padding(30)
+                .into()
+            }
+
+            Self::ShortcutHelp { previous } => stack![
+                previous.view_content(),
+                mouse_area(Space::new(Length::Fill, Length::Fill))
+                    .on_press(Message::ToggleShortcutHelp),
+                container(
+                    container(
+                        column![
+                            text(strings::UI_TITLE_SHORTCUTS).size(FONT_SIZE_SUBTITLE),
+                            column(SHORTCUTS.iter().map(|x| {
+                                row![
+                                    text(x.keys())
+                                        .font(Font {
+                                            weight: Weight::Bold,
+                                            ..Default::default()
+                                        })
+                                        .width(SHORTCUT_KEYS_WIDTH),
+                                    text(x.description),
+                                ]
+                                .into()
+                            }))
+                            .spacing(SPACING_TINY),
+                            button(strings::LABEL_CLOSE).on_press(Message::ToggleShortcutHelp),
+                        ]
+                        .spacing(SPACING_SMALL),
+                    )
+                    .padding(PADDING_SMALL)
+                    .style(container::bordered_box),
+                )
+                .center(Length::Fill),
+            ]
+            .into(),
+
+            Self::FatalError { error_description } => column![
+                text(strings::UI_TITLE_ERROR).font(Font {
+                    weight: Weight::Bold,
+                    ..Default::default()
+                }),
+                text(error_description).style(|theme: &iced::Theme| text::Style {
+                    color: Some(theme.palette().text.scale_alpha(0.5))
+                }),
+                row![
+                    button(strings::LABEL_RESTART).on_press(Message::ResetState),
+                    Space::with_width(Length::Fill),
+                    button(strings::LABEL_COPY)
+                        .on_press(Message::SetClipboardText(error_description.clone()))
+                ]
+            ]
+            .spacing(20)
+            .padding(30)
+            .into(),
+        }
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        if let NextArtView::ShortcutHelp { previous } = self
+            && !matches!(message, Message::ToggleShortcutHelp | Message::NavigateBack)
+        {
+            return previous.update(message);
+        }
+
+        if message.writes_to_disk()
+            && self
+                .config_ref()
+                .is_some_and(PersistentConfig::is_read_only)
+        {
+            return Task::done(Message::RecordError(strings::ERROR_READ_ONLY.into()));
+        }
+
         match message {
             Message::NoOp => {}
 
-            Message::SetRomInfoImage(width, height, byte_vec) => {
-                if let NextArtView::RomList { selected_image, .. } = self {
+            Message::SetRomInfoImage(width, height, byte_vec, has_alpha, frames) => {
+                if let NextArtView::RomList {
+                    state,
+                    selected_index,
+                    selected_image,
+                    ..
+                } = self
+                {
                     *selected_image = Some(image::Handle::from_rgba(width, height, byte_vec));
+                    if let Some(rom) = selected_index.and_then(|x| state.index.roms.get_mut(x)) {
+                        rom.boxart_has_alpha = Some(has_alpha);
+                        rom.boxart_dimensions = Some((width, height));
+                        rom.boxart_frames = Some(frames);
+                    }
                 }
             }
 
+            Message::OpenCollectionList | Message::OpenErrorList
+                if self.has_unapplied_matching_fill() =>
+            {
+                let previous = std::mem::replace(self, NextArtView::default());
+                *self = NextArtView::ConfirmDiscardMatchingFill {
+                    previous: Box::new(previous),
+                    destination: Box::new(message),
+                };
+            }
+
+            Message::DiscardMatchingFill => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::ConfirmDiscardMatchingFill {
+                    previous,
+                    destination,
+                } => {
+                    *self = *previous;
+                    if let NextArtView::RomList { matching_fill, .. } = self {
+                        *matching_fill = None;
+                    }
+                    return self.update(*destination);
+                }
+                other => *self = other,
+            },
+
+            Message::KeepMatchingFill => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::ConfirmDiscardMatchingFill { previous, .. } => *self = *previous,
+                other => *self = other,
+            },
+
             Message::OpenCollectionList => match std::mem::replace(self, NextArtView::default()) {
-                NextArtView::RomList { state, .. } | NextArtView::ErrorList { state } => {
-                    *self = NextArtView::CollectionList { state };
+                NextArtView::RomList { state, .. }
+                | NextArtView::ErrorList { state, .. }
+                | NextArtView::Settings { state, .. }
+                | NextArtView::RecentlyModified { state }
+                | NextArtView::ArtCheck { state, .. }
+                | NextArtView::OrphanedArt { state, .. } => {
+                    *self = NextArtView::CollectionList {
+                        state,
+                        renaming: None,
+                        dragging: None,
+                    };
                 }
                 other => {
                     *self = other;
@@ -538,8 +2518,12 @@ impl NextArtView {
             },
 
             Message::OpenErrorList => match std::mem::replace(self, NextArtView::default()) {
-                NextArtView::RomList { state, .. } | NextArtView::CollectionList { state } => {
-                    *self = NextArtView::ErrorList { state };
+                NextArtView::RomList { state, .. } | NextArtView::CollectionList { state, .. } => {
+                    *self = NextArtView::ErrorList {
+                        state,
+                        category: ErrorCategory::default(),
+                        group_by_collection: false,
+                    };
                 }
                 other => {
                     *self = other;
@@ -551,161 +2535,661 @@ impl NextArtView {
             },
 
             Message::ReplacementImageFromClip(boxart_path, rom_index) => {
+                // Art for a ROM without any still goes through the comparison, to adjust it first.
+                let has_current = self.has_boxart(rom_index);
+                let adjustment = self.config().art_adjustment;
                 return Task::perform(
                     async move {
-                        let mut clip = Clipboard::new().map_err(|e| {
-                            format!("{}{}", strings::ERROR_PREFIX_ACCESS_CLIPBOARD, e)
-                        })?;
-                        let image = clip.get_image().map_err(|e| {
-                            format!("{}{}", strings::ERROR_PREFIX_CLIPBOARD_IMAGE, e)
-                        })?;
-                        let rgba_image = RgbaImage::from_vec(
-                            image.width as u32,
-                            image.height as u32,
-                            image.bytes.to_vec(),
+                        let image = read_clipboard_image().await?;
+                        let remembered = ClipboardImage::new(image.clone());
+                        PendingReplacement::new(
+                            boxart_path,
+                            rom_index,
+                            image,
+                            None,
+                            adjustment,
+                            has_current,
                         )
-                        .ok_or_else(|| strings::ERROR_FAILED_CLIPBOARD_IMAGE_OTHER)?;
-                        rgba_image
-                            .save_with_format(&boxart_path, ::image::ImageFormat::Png)
-                            .map_err(|e| {
-                                format!(
-                                    "{}{}': {}",
-                                    strings::ERROR_PREFIX_SAVE_IMAGE,
-                                    boxart_path.display(),
-                                    e
-                                )
-                            })?;
-
-                        std::fs::metadata(&boxart_path)
-                            .map_err(|e| {
-                                format!(
-                                    "{}{}': {}",
-                                    strings::ERROR_PREFIX_GET_METADATA_SAVED,
-                                    boxart_path.display(),
-                                    e
-                                )
-                            })
-                            .map(|m| m.len())
+                        .map(|pending| (remembered, pending))
                     },
-                    move |result| match result {
-                        Ok(size) => Message::WroteNewImage(rom_index, size),
+                    |x| match x {
+                        Ok((remembered, pending)) => Message::RememberClipboardImage(
+                            remembered,
+                            Box::new(Message::CompareArt(Box::new(pending))),
+                        ),
                         Err(e) => Message::RecordError(e),
                     },
                 );
             }
 
-            Message::SetClipboardImage(image_path) => {
+            Message::ReplacementImageFromHistory(boxart_path, rom_index, clipboard_image) => {
+                let has_current = self.has_boxart(rom_index);
+                let adjustment = self.config().art_adjustment;
                 return Task::perform(
                     async move {
-                        let img = ImageReader::open(&image_path)
-                            .map_err(|x| {
-                                format!(
-                                    "{}{}': {}",
-                                    strings::ERROR_PREFIX_OPEN_IMAGE,
-                                    image_path.display(),
-                                    x
-                                )
-                            })?
-                            .decode()
-                            .map_err(|x| {
-                                format!("Failed to decode image '{}': {}", image_path.display(), x)
-                            })?
-                            .into_rgba8();
-                        let mut clip = Clipboard::new().map_err(|x| {
-                            format!("{}{}", strings::ERROR_PREFIX_ACCESS_CLIPBOARD, x)
-                        })?;
-                        let img_data = ImageData {
-                            width: img.width() as usize,
-                            height: img.height() as usize,
-                            bytes: img.as_bytes().into(),
-                        };
-
-                        clip.set_image(img_data).map_err(|x| {
-                            format!("{}{}", strings::ERROR_PREFIX_COPY_TO_CLIPBOARD, x)
-                        })
+                        PendingReplacement::new(
+                            boxart_path,
+                            rom_index,
+                            (*clipboard_image.image).clone(),
+                            None,
+                            adjustment,
+                            has_current,
+                        )
                     },
-                    |x: Result<(), String>| {
-                        if let Err(e) = x {
-                            Message::RecordError(e)
-                        } else {
-                            Message::NoOp
+                    |x| match x {
+                        Ok(pending) => Message::CompareArt(Box::new(pending)),
+                        Err(e) => Message::RecordError(e),
+                    },
+                );
+            }
+
+            Message::RememberClipboardImage(clipboard_image, next) => {
+                if let Some(state) = self.state_mut() {
+                    state.remember_clipboard_image(clipboard_image);
+                }
+                return self.update(*next);
+            }
+
+            Message::SetClipboardImage(image_path) => {
+                let warn_megapixels = self.config().clipboard_warn_megapixels;
+                let alpha = self.config().clipboard_alpha;
+                return Task::perform(
+                    async move {
+                        if let Megapixels(limit @ 1..) = warn_megapixels {
+                            let (width, height) = image_dimensions(&image_path)?;
+                            if width as u64 * height as u64 > limit as u64 * 1_000_000 {
+                                return Ok(Message::LargeClipboardImage(image_path, width, height));
+                            }
                         }
+
+                        copy_image_to_clipboard(&image_path, alpha)
+                            .await
+                            .map(|image| {
+                                Message::RememberClipboardImage(
+                                    ClipboardImage::new(image),
+                                    Box::new(Message::NoOp),
+                                )
+                            })
                     },
+                    |x: Result<Message, String>| x.unwrap_or_else(Message::RecordError),
                 );
             }
 
-            Message::WroteNewImage(rom_index, size) => {
+            Message::LargeClipboardImage(image_path, width, height) => {
                 if let NextArtView::RomList {
-                    state,
-                    selected_image,
+                    pending_clipboard_image,
                     ..
                 } = self
                 {
-                    state.index.roms[rom_index].boxart_size = size;
-                    *selected_image = None;
-
-                    if size != 0 {
-                        return Self::load_image_task(
-                            state.index.roms[rom_index].boxart_path.clone(),
-                        );
-                    }
+                    *pending_clipboard_image = Some(Box::new((image_path, width, height)));
                 }
             }
 
-            Message::DeleteBoxart(boxart_path, rom_index) => {
+            Message::ConfirmClipboardImage(image_path) => {
+                if let NextArtView::RomList {
+                    pending_clipboard_image,
+                    ..
+                } = self
+                {
+                    *pending_clipboard_image = None;
+                }
+                let alpha = self.config().clipboard_alpha;
                 return Task::perform(
-                    async move {
-                        match std::fs::remove_file(&boxart_path) {
-                            Ok(()) => Ok(rom_index),
-                            Err(e) => Err(format!(
-                                "{}{}: {e}",
-                                strings::ERROR_PREFIX_DELETE_FILE,
-                                boxart_path.display()
-                            )),
-                        }
-                    },
-                    |result| match result {
-                        Ok(index) => Message::WroteNewImage(index, 0),
+                    async move { copy_image_to_clipboard(&image_path, alpha).await },
+                    |x| match x {
+                        Ok(image) => Message::RememberClipboardImage(
+                            ClipboardImage::new(image),
+                            Box::new(Message::NoOp),
+                        ),
                         Err(e) => Message::RecordError(e),
                     },
                 );
             }
 
-            Message::ResetState => {
-                *self = NextArtView::Setup {
-                    chosen_path: None,
-                    error: None,
-                };
+            Message::CancelClipboardImage => {
+                if let NextArtView::RomList {
+                    pending_clipboard_image,
+                    ..
+                } = self
+                {
+                    *pending_clipboard_image = None;
+                }
             }
 
-            Message::OpenRomList(title, rom_indices) => {
-                match std::mem::replace(self, NextArtView::default()) {
-                    NextArtView::CollectionList { state } | NextArtView::ErrorList { state } => {
-                        *self = NextArtView::RomList {
-                            state,
-                            title,
-                            selected_index: None,
-                            selected_image: None,
-                            rom_indices,
-                        };
+            Message::WroteNewImage(rom_index, size) => {
+                log::info!("Wrote box art for ROM {rom_index} ({size} bytes)");
+                // Undo and redo can finish after leaving the ROM list, so any view's state is updated.
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                let Some(rom) = state.index.roms.get_mut(rom_index) else {
+                    // Gone from the index since the write started, e.g. after a reindex.
+                    if let NextArtView::RomList { saving, .. } = self {
+                        *saving = false;
                     }
-                    other => {
-                        *self = other;
-                        return Task::perform(
-                            async { String::from("Cannot navigate: No state available") },
-                            Message::RecordError,
-                        );
+                    return Task::none();
+                };
+                rom.boxart_size = size;
+                rom.boxart_modified = (size != 0).then(SystemTime::now);
+                rom.boxart_has_alpha = None;
+                rom.boxart_frames = None;
+                rom.boxart_dimensions = None;
+                let boxart_path = rom.boxart_path.clone();
+                state.mark_rom_written(rom_index);
+                state.recently_modified.retain(|x| *x != rom_index);
+                state.recently_modified.push(rom_index);
+                state.thumbnails.remove(&boxart_path);
+                let thumbnail = if size != 0 && state.config.rom_layout == RomLayout::Gallery {
+                    Self::load_thumbnails_task(vec![boxart_path.clone()], &state.config)
+                } else {
+                    Task::none()
+                };
+                let read_buffer_size = state.config.read_buffer_size;
+
+                if let NextArtView::RomList {
+                    selected_index,
+                    selected_image,
+                    saving,
+                    ..
+                } = self
+                {
+                    *saving = false;
+                    if *selected_index != Some(rom_index) {
+                        return thumbnail;
                     }
-                }
-            }
+                    *selected_image = None;
 
-            Message::RecordError(error_description) => {
-                if let NextArtView::RomList { state, .. } = self {
-                    state.errors.push(error_description);
-                } else if let NextArtView::Loading { state, .. } = self {
-                    state.errors.push(error_description);
-                } else if let NextArtView::CollectionList { state } = self {
-                    state.errors.push(error_description);
+                    if size != 0 {
+                        return Task::batch([
+                            thumbnail,
+                            Self::load_image_task(boxart_path, read_buffer_size),
+                        ]);
+                    }
+                }
+                return thumbnail;
+            }
+
+            Message::ToggleRomSelection(rom_index, selected) => {
+                if let NextArtView::RomList { selected_roms, .. } = self {
+                    if selected {
+                        selected_roms.insert(rom_index);
+                    } else {
+                        selected_roms.remove(&rom_index);
+                    }
+                }
+            }
+
+            Message::SelectRoms(rom_indices) => {
+                if let NextArtView::RomList { selected_roms, .. } = self {
+                    selected_roms.extend(rom_indices);
+                }
+            }
+
+            Message::ClearRomSelection => {
+                if let NextArtView::RomList { selected_roms, .. } = self {
+                    selected_roms.clear();
+                }
+            }
+
+            Message::ApplyImageToSelected(source, rom_indices) => {
+                let Some(state) = self.state() else {
+                    return Task::none();
+                };
+                let config = state.config.clone();
+                let start_dir = config.last_image_dir.clone();
+                // Only ROMs whose collection has a manifest get their own copy of the settings.
+                let targets: Vec<(usize, PathBuf, WriteTicket, Option<PersistentConfig>)> =
+                    rom_indices
+                        .into_iter()
+                        .filter_map(|x| {
+                            let boxart_path = state.index.roms.get(x)?.boxart_path.clone();
+                            let ticket = WriteTicket::reserve(&boxart_path);
+                            let rom_config = match state.rom_config(x) {
+                                Cow::Owned(rom_config) => Some(rom_config),
+                                Cow::Borrowed(_) => None,
+                            };
+                            Some((x, boxart_path, ticket, rom_config))
+                        })
+                        .collect();
+
+                return Task::run(
+                    iced::stream::channel(16, move |mut output| async move {
+                        let result: Result<(Option<PathBuf>, BatchWriteResults), String> = async {
+                            let mut picked = None;
+                            let image = match source {
+                                ImageSource::Clipboard => {
+                                    let image = read_clipboard_image().await?;
+                                    let _ = output
+                                        .send(Message::RememberClipboardImage(
+                                            ClipboardImage::new(image.clone()),
+                                            Box::new(Message::NoOp),
+                                        ))
+                                        .await;
+                                    image
+                                }
+                                ImageSource::File => open_image(
+                                    picked.insert(pick_image_file(start_dir.as_deref())?),
+                                )?,
+                                ImageSource::Path(path) => open_image(&path)?,
+                            };
+
+                            let total = targets.len();
+                            let mut results = Vec::with_capacity(total);
+                            let _ = output.send(Message::BulkProgress(0, total)).await;
+                            for (rom_index, boxart_path, mut ticket, rom_config) in targets {
+                                ticket.ready().await;
+                                let config = rom_config.as_ref().unwrap_or(&config);
+                                results.push((
+                                    rom_index,
+                                    write_undoable(rom_index, &boxart_path, || {
+                                        save_boxart(image.clone(), &boxart_path, config)
+                                    }),
+                                ));
+                                let _ = output
+                                    .send(Message::BulkProgress(results.len(), total))
+                                    .await;
+                            }
+                            Ok((picked, results))
+                        }
+                        .await;
+
+                        let _ = output
+                            .send(match result {
+                                Ok((Some(picked), results)) => Message::ImagePicked(
+                                    picked,
+                                    Box::new(Message::AppliedImageToSelected(results)),
+                                ),
+                                Ok((None, results)) => Message::AppliedImageToSelected(results),
+                                Err(e) => Message::RecordError(e),
+                            })
+                            .await;
+                    }),
+                    std::convert::identity,
+                );
+            }
+
+            Message::FetchArtForSelected(rom_indices) => {
+                let Some(state) = self.state() else {
+                    return Task::none();
+                };
+                let Some(source) = state.config.art_source() else {
+                    return Task::done(Message::RecordError(strings::ERROR_NO_ART_SOURCE.into()));
+                };
+                let config = state.config.clone();
+                let (source_kind, location) = (
+                    config.art_source,
+                    config.art_source_location.trim().to_owned(),
+                );
+                let targets: Vec<_> = rom_indices
+                    .into_iter()
+                    .filter_map(|x| {
+                        let rom = state.index.roms.get(x)?;
+                        let ticket = WriteTicket::reserve(&rom.boxart_path);
+                        let rom_config = match state.rom_config(x) {
+                            Cow::Owned(rom_config) => Some(rom_config),
+                            Cow::Borrowed(_) => None,
+                        };
+                        // The cleaned-up name is tried second, as sources rarely keep every tag.
+                        let mut rom_names = vec![rom.name.clone()];
+                        if rom.display_name != rom.name {
+                            rom_names.push(rom.display_name.clone());
+                        }
+                        Some((x, rom_names, rom.boxart_path.clone(), ticket, rom_config))
+                    })
+                    .collect();
+
+                return Task::run(
+                    iced::stream::channel(16, move |mut output| async move {
+                        let target_paths = targets.iter().map(|x| x.2.clone()).collect();
+                        let mut journal =
+                            ImportJournal::resume(source_kind, &location, target_paths);
+                        let total = targets.len();
+                        let targets: Vec<_> = targets
+                            .into_iter()
+                            .filter(|(_, _, boxart_path, ..)| !journal.is_done(boxart_path))
+                            .collect();
+                        let resumed = total - targets.len();
+                        let mut results: BatchWriteResults = Vec::with_capacity(targets.len());
+                        let _ = output.send(Message::BulkProgress(resumed, total)).await;
+                        for (rom_index, rom_names, boxart_path, mut ticket, rom_config) in targets {
+                            let source = source.clone();
+                            let fetched = tokio::task::spawn_blocking(move || {
+                                let bytes = source.fetch_first(&rom_names)?;
+                                ::image::load_from_memory(&bytes)
+                                    .map_err(|e| decode_error(Path::new(&rom_names[0]), e))
+                            })
+                            .await
+                            .map_err(|e| e.to_string())
+                            .flatten();
+
+                            ticket.ready().await;
+                            let config = rom_config.as_ref().unwrap_or(&config);
+                            let result = fetched.and_then(|image| {
+                                write_undoable(rom_index, &boxart_path, || {
+                                    save_boxart(image, &boxart_path, config)
+                                })
+                            });
+                            // Failures are left out of the journal so a resumed run tries them again.
+                            if result.is_ok() {
+                                journal.fetched.insert(boxart_path);
+                                if journal
+                                    .fetched
+                                    .len()
+                                    .is_multiple_of(IMPORT_JOURNAL_SAVE_INTERVAL)
+                                    && let Err(e) = journal.save()
+                                {
+                                    log::warn!("{e}");
+                                }
+                            }
+                            results.push((rom_index, result));
+                            let _ = output
+                                .send(Message::BulkProgress(resumed + results.len(), total))
+                                .await;
+                        }
+                        if let Err(e) = ImportJournal::remove() {
+                            log::warn!("{e}");
+                        }
+                        let _ = output.send(Message::AppliedImageToSelected(results)).await;
+                    }),
+                    std::convert::identity,
+                );
+            }
+
+            Message::BulkProgress(done, total) => {
+                if let Some(state) = self.state_mut() {
+                    state.bulk_progress = Some((done, total));
+                }
+            }
+
+            Message::AppliedImageToSelected(results) => {
+                let mut changes = Vec::new();
+                let messages: Vec<Task<Message>> = results
+                    .into_iter()
+                    .map(|(rom_index, result)| {
+                        Task::done(match result {
+                            Ok((change, size)) => {
+                                changes.push(change);
+                                Message::WroteNewImage(rom_index, size)
+                            }
+                            Err(e) => Message::RecordError(e),
+                        })
+                    })
+                    .collect();
+                if let Some(state) = self.state_mut() {
+                    state.bulk_progress = None;
+                    // Applied together, so undone together.
+                    state.push_undo(UndoStep::Art(changes));
+                }
+                return Task::batch(messages);
+            }
+
+            Message::ArtChanged(change, size) => {
+                let rom_index = change.rom_index;
+                if let Some(state) = self.state_mut() {
+                    state.push_undo(UndoStep::Art(vec![change]));
+                }
+                return self.update(Message::WroteNewImage(rom_index, size));
+            }
+
+            Message::Undo => return self.restore_history(true),
+
+            Message::Redo => return self.restore_history(false),
+
+            Message::RestoredArt(results) => {
+                return Task::batch(results.into_iter().map(|(rom_index, result)| {
+                    Task::done(match result {
+                        Ok(size) => Message::WroteNewImage(rom_index, size),
+                        Err(e) => Message::RecordError(e),
+                    })
+                }));
+            }
+
+            Message::RestoredRenames(results) => {
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                let mut messages = Vec::with_capacity(results.len());
+                for (rom_index, boxart_path, result) in results {
+                    match result {
+                        Ok(size) => {
+                            if let Some(boxart_path) = boxart_path
+                                && let Some(rom) = state.index.roms.get_mut(rom_index)
+                            {
+                                rom.boxart_path = boxart_path;
+                            }
+                            messages.push(Task::done(Message::WroteNewImage(rom_index, size)));
+                        }
+                        Err(e) => messages.push(Task::done(Message::RecordError(e))),
+                    }
+                }
+                self.recount_stale_boxart_paths();
+                return Task::batch(messages);
+            }
+
+            Message::DeleteBoxart(boxart_path, rom_index) => {
+                if !self.begin_saving() {
+                    return Task::none();
+                }
+                let mut ticket = WriteTicket::reserve(&boxart_path);
+                return Task::perform(
+                    async move {
+                        ticket.ready().await;
+                        write_undoable(rom_index, &boxart_path, || delete_boxart(&boxart_path))
+                    },
+                    |result| match result {
+                        Ok((change, size)) => Message::ArtChanged(change, size),
+                        Err(e) => Message::RecordError(e),
+                    },
+                );
+            }
+
+            Message::RequestBulkDelete(rom_indices) => {
+                match std::mem::replace(self, NextArtView::default()) {
+                    rom_list @ NextArtView::RomList { .. } => {
+                        let roms = &rom_list.state().expect("RomList has state").index.roms;
+                        let rom_indices = rom_indices
+                            .into_iter()
+                            .filter(|x| roms.get(*x).is_some_and(|rom| rom.boxart_size != 0))
+                            .collect();
+                        *self = NextArtView::ConfirmBulkDelete {
+                            previous: Box::new(rom_list),
+                            rom_indices,
+                        };
+                    }
+                    other => *self = other,
+                }
+            }
+
+            Message::ConfirmBulkDelete => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::ConfirmBulkDelete {
+                    previous,
+                    rom_indices,
+                } => {
+                    *self = *previous;
+                    let Some(state) = self.state() else {
+                        return Task::none();
+                    };
+                    let targets: Vec<(usize, PathBuf, WriteTicket)> = rom_indices
+                        .into_iter()
+                        .filter_map(|x| {
+                            let boxart_path = state.index.roms.get(x)?.boxart_path.clone();
+                            let ticket = WriteTicket::reserve(&boxart_path);
+                            Some((x, boxart_path, ticket))
+                        })
+                        .collect();
+
+                    return Task::run(
+                        iced::stream::channel(16, move |mut output| async move {
+                            let total = targets.len();
+                            let mut results = Vec::with_capacity(total);
+                            let _ = output.send(Message::BulkProgress(0, total)).await;
+                            for (rom_index, boxart_path, mut ticket) in targets {
+                                ticket.ready().await;
+                                results.push((
+                                    rom_index,
+                                    write_undoable(rom_index, &boxart_path, || {
+                                        delete_boxart(&boxart_path)
+                                    }),
+                                ));
+                                let _ = output
+                                    .send(Message::BulkProgress(results.len(), total))
+                                    .await;
+                            }
+                            let _ = output.send(Message::AppliedImageToSelected(results)).await;
+                        }),
+                        std::convert::identity,
+                    );
+                }
+                other => *self = other,
+            },
+
+            Message::CancelBulkDelete => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::ConfirmBulkDelete { previous, .. } => *self = *previous,
+                other => *self = other,
+            },
+
+            Message::ResetState => {
+                let (config, error) = match PersistentConfig::load() {
+                    Ok(config) => (config.unwrap_or_default(), None),
+                    Err(e) => (PersistentConfig::default(), Some(e)),
+                };
+
+                *self = NextArtView::Setup {
+                    chosen_path: None,
+                    config,
+                    error,
+                };
+            }
+
+            Message::ReturnToSetup => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    *self = NextArtView::Setup {
+                        chosen_path: Some(state.roms_folder.clone()),
+                        config: state.config.clone(),
+                        error: None,
+                    };
+                }
+            }
+
+            Message::SetErrorCategory(new_category) => {
+                if let NextArtView::ErrorList { category, .. } = self {
+                    *category = new_category;
+                }
+            }
+
+            Message::SetGroupErrorsByCollection(group) => {
+                if let NextArtView::ErrorList {
+                    group_by_collection,
+                    ..
+                } = self
+                {
+                    *group_by_collection = group;
+                }
+            }
+
+            Message::OpenRomList(title, rom_indices) => {
+                match std::mem::replace(self, NextArtView::default()) {
+                    NextArtView::CollectionList { state, .. }
+                    | NextArtView::ErrorList { state, .. }
+                    | NextArtView::RecentlyModified { state } => {
+                        *self = NextArtView::RomList {
+                            state,
+                            title,
+                            selected_index: None,
+                            selected_image: None,
+                            rom_indices,
+                            cursor_position: Point::ORIGIN,
+                            context_menu: None,
+                            selected_roms: BTreeSet::new(),
+                            pending_clipboard_image: None,
+                            filter: RomFilter::default(),
+                            saving: false,
+                            external_edit: None,
+                            rescanning: false,
+                            matching_fill: None,
+                            resizing_panes: None,
+                        };
+                        return self.load_missing_thumbnails();
+                    }
+                    other => {
+                        *self = other;
+                        return Task::perform(
+                            async { String::from("Cannot navigate: No state available") },
+                            Message::RecordError,
+                        );
+                    }
+                }
+            }
+
+            Message::RecordError(error_description) => {
+                log::error!("{error_description}");
+                match self {
+                    NextArtView::Setup { error, .. } => {
+                        // Errors from tasks that outlived the previous state would otherwise be lost.
+                        *error = Some(match error.take() {
+                            Some(existing) => format!("{existing}\n{error_description}"),
+                            None => error_description,
+                        });
+                    }
+                    NextArtView::FatalError {
+                        error_description: existing,
+                    } => {
+                        existing.push('\n');
+                        existing.push_str(&error_description);
+                    }
+                    other => {
+                        if let NextArtView::RomList { saving, .. } = other {
+                            *saving = false;
+                        }
+                        if let Some(state) = other.state_mut() {
+                            state.toast = Some((error_description.clone(), Instant::now()));
+                            state.errors.push(error_description);
+                        }
+                    }
+                }
+            }
+
+            Message::OpenSettings => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::CollectionList { state, .. } => {
+                    *self = NextArtView::Settings {
+                        boxart_template: state.config.boxart_template.clone(),
+                        excluded_collections: state.config.excluded_collections.join(", "),
+                        extension_systems: state.config.extension_systems_text(),
+                        system_categories: state.config.system_categories_text(),
+                        stale_boxart_paths: state.stale_boxart_path_count(),
+                        state,
+                    };
+                }
+                other => {
+                    *self = other;
+                    return Task::perform(
+                        async { String::from(strings::ERROR_CANNOT_NAVIGATE) },
+                        Message::RecordError,
+                    );
+                }
+            },
+
+            Message::SetSizeUnits(size_units) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.size_units = size_units;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::ExpireToast(now) => {
+                if let Some(state) = self.state_mut()
+                    && state
+                        .toast
+                        .as_ref()
+                        .is_some_and(|(_, shown)| now.duration_since(*shown) >= TOAST_DURATION)
+                {
+                    state.toast = None;
+                }
+            }
+
+            Message::DismissToast => {
+                if let Some(state) = self.state_mut() {
+                    state.toast = None;
                 }
             }
 
@@ -718,37 +3202,44 @@ impl NextArtView {
             }
 
             Message::ChooseReplacementImage(path, rom_index) => {
+                let start_dir = self.config().last_image_dir;
+                let adjustment = self.config().art_adjustment;
+                let has_current = self.has_boxart(rom_index);
                 return Task::perform(
                     async move {
-                        let dialog = FileDialog::new().add_filter("PNG", &["png"]);
-                        if let Some(picked) = dialog.pick_file() {
-                            let written = std::fs::copy(&picked, &path);
-                            if let Ok(written) = written {
-                                return Ok(written);
-                            } else {
-                                return Err(format!(
-                                    "{}{}' to '{}': {}",
-                                    strings::ERROR_PREFIX_COPY_FILE,
-                                    picked.display(),
-                                    path.display(),
-                                    written.unwrap_err()
-                                ));
-                            }
-                        } else {
-                            return Err(strings::ERROR_NO_FILE_SELECTED.into());
-                        }
+                        let picked = pick_image_file(start_dir.as_deref())?;
+                        let replacement = open_image(&picked)?;
+                        PendingReplacement::new(
+                            path,
+                            rom_index,
+                            replacement,
+                            Some(picked.clone()),
+                            adjustment,
+                            has_current,
+                        )
+                        .map(|pending| (picked, pending))
                     },
-                    move |x| match x {
-                        Ok(x) => Message::WroteNewImage(rom_index, x),
-                        Err(e) => Message::RecordError(e.to_string()),
+                    |x| match x {
+                        Ok((picked, pending)) => Message::ImagePicked(
+                            picked,
+                            Box::new(Message::CompareArt(Box::new(pending))),
+                        ),
+                        Err(e) => Message::RecordError(e),
                     },
                 );
             }
 
             Message::OpenRomDirectoryPicker => {
+                let start_dir = match self {
+                    NextArtView::Setup { chosen_path, .. } => chosen_path.clone(),
+                    _ => None,
+                };
                 return Task::perform(
                     async move {
-                        let dialog = FileDialog::new();
+                        let mut dialog = FileDialog::new();
+                        if let Some(start_dir) = start_dir {
+                            dialog = dialog.set_directory(start_dir);
+                        }
                         dialog.pick_folder()
                     },
                     |x| {
@@ -767,6 +3258,112 @@ impl NextArtView {
                 }
             }
 
+            Message::OpenArtRootPicker => {
+                let start_dir = match self {
+                    NextArtView::Setup {
+                        chosen_path,
+                        config,
+                        ..
+                    } => config.art_root.clone().or_else(|| chosen_path.clone()),
+                    _ => None,
+                };
+                return Task::perform(
+                    async move {
+                        let mut dialog = FileDialog::new();
+                        if let Some(start_dir) = start_dir {
+                            dialog = dialog.set_directory(start_dir);
+                        }
+                        dialog.pick_folder()
+                    },
+                    |x| {
+                        if let Some(x) = x {
+                            Message::ArtRootChosen(Some(x))
+                        } else {
+                            Message::NoOp
+                        }
+                    },
+                );
+            }
+
+            Message::ArtRootChosen(path) => {
+                if let NextArtView::Setup { config, .. } = self {
+                    config.art_root = path;
+                }
+            }
+
+            Message::ExportSettings => {
+                let config = self.config();
+                return Task::perform(
+                    async move {
+                        let Some(path) = FileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .set_file_name(SETTINGS_EXPORT_FILE_NAME)
+                            .save_file()
+                        else {
+                            return Ok(());
+                        };
+                        config.export_to(&path)
+                    },
+                    |result| match result {
+                        Ok(()) => Message::NoOp,
+                        Err(e) => Message::RecordError(e),
+                    },
+                );
+            }
+
+            Message::ImportSettings => {
+                return Task::perform(
+                    async move {
+                        FileDialog::new()
+                            .add_filter("JSON", &["json"])
+                            .pick_file()
+                            .map(|path| PersistentConfig::import_from(&path))
+                    },
+                    |result| match result {
+                        Some(Ok(config)) => Message::ImportedSettings(Box::new(config)),
+                        Some(Err(e)) => Message::RecordError(e),
+                        None => Message::NoOp,
+                    },
+                );
+            }
+
+            Message::ImportedSettings(imported) => {
+                if let NextArtView::Setup {
+                    chosen_path,
+                    config,
+                    error,
+                } = self
+                {
+                    *config = *imported;
+                    let mut notes = Vec::new();
+
+                    // Paths from another machine often don't exist here, so keep the folder chosen so far.
+                    if config.roms_path.is_dir() {
+                        *chosen_path = Some(config.roms_path.clone());
+                    } else {
+                        notes.push(format!(
+                            "{}{}'{}",
+                            strings::ERROR_PREFIX_IMPORTED_ROMS_PATH,
+                            config.roms_path.display(),
+                            strings::ERROR_SUFFIX_IMPORTED_ROMS_PATH
+                        ));
+                    }
+                    if let Some(art_root) = &config.art_root
+                        && !art_root.is_dir()
+                    {
+                        notes.push(format!(
+                            "{}{}'{}",
+                            strings::ERROR_PREFIX_IMPORTED_ART_ROOT,
+                            art_root.display(),
+                            strings::ERROR_SUFFIX_IMPORTED_ART_ROOT
+                        ));
+                        config.art_root = None;
+                    }
+
+                    *error = (!notes.is_empty()).then(|| notes.join("\n"));
+                }
+            }
+
             Message::SelectRom(index) => {
                 if let NextArtView::RomList {
                     selected_index,
@@ -777,100 +3374,2266 @@ impl NextArtView {
                     *selected_index = Some(index);
 
                     if state.index.roms[index].boxart_size != 0 {
-                        return Self::load_image_task(state.index.roms[index].boxart_path.clone());
+                        return Self::load_image_task(
+                            state.index.roms[index].boxart_path.clone(),
+                            state.config.read_buffer_size,
+                        );
                     }
                 }
             }
 
+            Message::ManageRom(rom_index) => {
+                let select = self.update(Message::SelectRom(rom_index));
+                let config = self.config();
+                // Read-only mode would only turn the action into an error on every click.
+                let source = match config.default_rom_action {
+                    _ if config.is_read_only() => return select,
+                    DefaultRomAction::Preview => return select,
+                    DefaultRomAction::ChooseFile => ImageSource::File,
+                    DefaultRomAction::PasteClipboard => ImageSource::Clipboard,
+                };
+                return select.chain(Task::done(Message::ReplaceCurrentImage(source)));
+            }
+
             Message::SetupDone(path) => {
+                let config = PersistentConfig {
+                    roms_path: path.clone(),
+                    ..if let NextArtView::Setup { config, .. } = self {
+                        config.clone()
+                    } else {
+                        PersistentConfig::default()
+                    }
+                };
+
                 *self = NextArtView::Loading {
-                    state: State {
-                        roms_folder: path,
-                        errors: Vec::new(),
-                        index: Index::default(),
-                    },
+                    state: State::new(nextart::State::new(path, config)),
                     message: strings::UI_SETUP_INDEXING.into(),
                 };
                 if let Self::Loading { state, .. } = self {
-                    let mut state = state.clone();
-                    return Task::perform(
-                        async move {
-                            if let Some(dirs) =
-                                ProjectDirs::from("", strings::DIR_ORG, strings::DIR_APP)
-                            {
-                                let config_dir = dirs.config_dir();
-
-                                if let Err(e) = std::fs::create_dir_all(config_dir) {
-                                    state.errors.push(format!(
-                                        "{}: {}",
-                                        strings::ERROR_PREFIX_CONFIG_DIR_CREATE,
-                                        e
-                                    ));
-                                } else {
-                                    let config = PersistentConfig {
-                                        roms_path: state.roms_folder.clone(),
-                                    };
-
-                                    let config_path = config_dir.join("config.json");
-
-                                    if let Err(e) = serde_json::to_string(&config)
-                                        .map_err(|e| {
-                                            format!(
-                                                "{}: {}",
-                                                strings::ERROR_PREFIX_CONFIG_FILE_CREATE,
-                                                e
-                                            )
-                                        })
-                                        .and_then(|serialized| {
-                                            std::fs::write(&config_path, serialized).map_err(|e| {
-                                                format!(
-                                                    "{}: {}",
-                                                    strings::ERROR_PREFIX_CONFIG_FILE_CREATE,
-                                                    e
-                                                )
-                                            })
-                                        })
-                                    {
-                                        state.errors.push(e);
-                                    }
+                    let mut state = state.library.clone();
+                    return Task::run(
+                        iced::stream::channel(16, move |mut output| async move {
+                            let mut progress = output.clone();
+                            let indexed = tokio::task::spawn_blocking(move || {
+                                if let Err(e) = state.config.save() {
+                                    state.errors.push(e);
                                 }
-                            } else {
-                                state.errors.push(strings::ERROR_NO_HOME_DIRECTORY.into());
-                            }
 
-                            // Index ROMs
-                            if let Err(e) = state.index_roms() {
-                                state.errors.push(e.to_string());
+                                // Index ROMs
+                                let mut on_slow = |warning| {
+                                    // Dropping a warning is fine if the UI is behind.
+                                    let _ = progress.try_send(Message::IndexingProgress(warning));
+                                };
+                                if let Err(e) = state.index_roms(&mut on_slow) {
+                                    state.errors.push(e.to_string());
+                                }
+
+                                match CoverageSnapshot::load() {
+                                    Ok(snapshot) => state.previous_coverage = snapshot,
+                                    Err(e) => state.errors.push(e),
+                                }
+
+                                state
+                            })
+                            .await;
+
+                            let _ = output
+                                .send(match indexed {
+                                    Ok(state) => Message::CompletedIndexing(Box::new(state)),
+                                    Err(e) => Message::ViewError(e.to_string()),
+                                })
+                                .await;
+                        }),
+                        std::convert::identity,
+                    );
+                }
+            }
+
+            Message::IndexingProgress(warning) => {
+                if let NextArtView::Loading { message, .. } = self {
+                    *message = format!("{}\n\n{}", strings::UI_SETUP_INDEXING, warning);
+                }
+            }
+
+            Message::CloseRequested(id) => {
+                // Closing now could cut off a write and leave half-written art behind.
+                let in_flight = WRITES_IN_FLIGHT.load(Ordering::SeqCst);
+                if in_flight > 0 && !self.state().is_some_and(|x| x.close_warned) {
+                    log::info!("Waiting for {in_flight} writes to finish before closing");
+                    return Task::perform(wait_for_writes(SHUTDOWN_WRITE_WAIT), move |finished| {
+                        if finished {
+                            Message::CloseRequested(id)
+                        } else {
+                            Message::WritesStillPending(id)
+                        }
+                    });
+                }
+
+                // Debounced note saves may still be waiting, so write them now.
+                for (note_path, (_, note)) in PENDING_NOTES.lock().unwrap().drain() {
+                    if let Err(e) = write_note(&note_path, &note) {
+                        log::error!("{e}");
+                    }
+                }
+
+                // The index is still empty while loading, which would wipe the last snapshot.
+                if !matches!(self, NextArtView::Loading { .. })
+                    && let Some(state) = self.state()
+                    && !state.config.is_read_only()
+                {
+                    // A debounced save may still be waiting, so write the latest config now.
+                    CONFIG_SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+                    for result in [
+                        state.config.save(),
+                        CoverageSnapshot::from_index(&state.index).save(),
+                    ] {
+                        if let Err(e) = result {
+                            log::error!("{e}");
+                        }
+                    }
+                }
+                if let Err(e) = std::fs::remove_dir_all(undo_folder())
+                    && e.kind() != io::ErrorKind::NotFound
+                {
+                    log::warn!("Failed to remove undo copies: {e}");
+                }
+                return window::close(id);
+            }
+
+            Message::WritesStillPending(id) => {
+                let Some(state) = self.state_mut() else {
+                    // Nothing to warn in, so close rather than leave the request unanswered.
+                    return window::close(id);
+                };
+                state.close_warned = true;
+                return self.update(Message::RecordError(format!(
+                    "{}{}{}",
+                    strings::ERROR_PREFIX_WRITES_PENDING,
+                    WRITES_IN_FLIGHT.load(Ordering::SeqCst),
+                    strings::ERROR_SUFFIX_WRITES_PENDING
+                )));
+            }
+
+            Message::CompletedIndexing(state) => {
+                *self = NextArtView::CollectionList {
+                    state: State::new(*state),
+                    renaming: None,
+                    dragging: None,
+                };
+            }
+
+            Message::CursorMoved(position) => {
+                if let NextArtView::RomList {
+                    state,
+                    cursor_position,
+                    resizing_panes,
+                    ..
+                } = self
+                {
+                    *cursor_position = position;
+                    if let Some((start_x, start_ratio, Some(width))) = resizing_panes.as_deref() {
+                        state.config.pane_ratio = (*start_ratio + (position.x - *start_x) / *width)
+                            .clamp(MIN_PANE_RATIO, 1.0 - MIN_PANE_RATIO);
+                    }
+                }
+            }
+
+            Message::StartResizingPanes => {
+                if let NextArtView::RomList {
+                    state,
+                    cursor_position,
+                    resizing_panes,
+                    ..
+                } = self
+                {
+                    *resizing_panes =
+                        Some(Box::new((cursor_position.x, state.config.pane_ratio, None)));
+                    return window::get_latest()
+                        .and_then(window::get_size)
+                        .map(Message::MeasuredPanes);
+                }
+            }
+
+            Message::MeasuredPanes(window_size) => {
+                let scale_factor = self.scale_factor() as f32;
+                if let NextArtView::RomList {
+                    resizing_panes: Some(resizing),
+                    ..
+                } = self
+                {
+                    let (_, _, width) = &mut **resizing;
+                    // The window less the view's padding, the panes' padding, and the divider.
+                    let chrome =
+                        2.0 * f32::from(PADDING_STANDARD + PADDING_SMALL) + PANE_DIVIDER_WIDTH;
+                    *width = Some((window_size.width / scale_factor - chrome).max(1.0));
+                }
+            }
+
+            Message::FinishResizingPanes => {
+                if let NextArtView::RomList {
+                    state,
+                    resizing_panes,
+                    ..
+                } = self
+                    && resizing_panes.take().is_some()
+                {
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::OpenContextMenu(rom_index) => {
+                if let NextArtView::RomList {
+                    cursor_position,
+                    context_menu,
+                    ..
+                } = self
+                {
+                    *context_menu = Some(ContextMenu {
+                        rom_index,
+                        position: *cursor_position,
+                    });
+                }
+            }
+
+            Message::CloseContextMenu => {
+                if let NextArtView::RomList { context_menu, .. } = self {
+                    *context_menu = None;
+                }
+            }
+
+            Message::SetImportResize(import_resize) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.import_resize = import_resize;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetResizeFilter(resize_filter) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.resize_filter = resize_filter;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetPngColorType(png_color_type) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.png_color_type = png_color_type;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetClipboardWarnMegapixels(megapixels) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.clipboard_warn_megapixels = megapixels;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetBoxartTemplate(template) => {
+                if let NextArtView::Settings {
+                    state,
+                    boxart_template,
+                    ..
+                } = self
+                {
+                    *boxart_template = template.clone();
+                    if PersistentConfig::is_valid_boxart_template(&template) {
+                        state.config.boxart_template = template;
+                        let config = state.config.clone();
+                        self.recount_stale_boxart_paths();
+                        return Self::save_config_task(config);
+                    }
+                }
+            }
+
+            Message::SetGroupByExtension(group_by_extension) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.group_by_extension = group_by_extension;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetExtensionSystems(pairs) => {
+                if let NextArtView::Settings {
+                    state,
+                    extension_systems,
+                    ..
+                } = self
+                {
+                    state.config.extension_systems = pairs
+                        .split(',')
+                        .filter_map(|pair| pair.split_once('='))
+                        .map(|(extension, system)| {
+                            (
+                                extension.trim().trim_start_matches('.').to_lowercase(),
+                                system.trim(),
+                            )
+                        })
+                        .filter(|(extension, system)| !extension.is_empty() && !system.is_empty())
+                        .map(|(extension, system)| (extension, system.to_owned()))
+                        .collect();
+                    *extension_systems = pairs;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetSystemCategories(pairs) => {
+                if let NextArtView::Settings {
+                    state,
+                    system_categories,
+                    ..
+                } = self
+                {
+                    state.config.system_categories = pairs
+                        .split(',')
+                        .filter_map(|pair| pair.split_once('='))
+                        .map(|(system, category)| (system.trim().to_uppercase(), category.trim()))
+                        .filter(|(system, category)| !system.is_empty() && !category.is_empty())
+                        .map(|(system, category)| (system, category.to_owned()))
+                        .collect();
+                    *system_categories = pairs;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetExcludedCollections(patterns) => {
+                if let NextArtView::Settings {
+                    state,
+                    excluded_collections,
+                    ..
+                } = self
+                {
+                    state.config.excluded_collections = patterns
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|x| !x.is_empty())
+                        .map(String::from)
+                        .collect();
+                    *excluded_collections = patterns;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::MigrateBoxartTemplate => {
+                if let NextArtView::Settings { state, .. } = self {
+                    let renames: Vec<(usize, PathBuf, PathBuf, bool)> = state
+                        .index
+                        .listed_roms()
+                        .map(|(i, rom)| {
+                            (
+                                i,
+                                rom.boxart_path.clone(),
+                                state.templated_boxart_path(i, rom),
+                                rom.boxart_size != 0,
+                            )
+                        })
+                        .filter(|(_, old, new, _)| old != new)
+                        .collect();
+                    let png_color_type = state.config.png_color_type;
+
+                    return Task::perform(
+                        async move {
+                            renames
+                                .into_iter()
+                                .map(|(rom_index, old, new, has_art)| {
+                                    if !has_art {
+                                        return (rom_index, Ok((new, 0)));
+                                    }
+                                    // Re-encoding for a new extension changes the size.
+                                    let result = rename_art(&old, &new, png_color_type).map(|()| {
+                                        let size = std::fs::metadata(&new).map_or(0, |x| x.len());
+                                        (new, size)
+                                    });
+                                    (rom_index, result)
+                                })
+                                .collect()
+                        },
+                        Message::MigratedBoxartTemplate,
+                    );
+                }
+            }
+
+            Message::MigratedBoxartTemplate(results) => {
+                if let Some(state) = self.state_mut() {
+                    let mut renames = Vec::new();
+                    for (rom_index, result) in results {
+                        match result {
+                            Ok((new_path, size)) => {
+                                let rom = &mut state.index.roms[rom_index];
+                                // ROMs without art only had their path updated, with nothing to move back.
+                                if rom.boxart_size != 0 {
+                                    renames.push(ArtRename {
+                                        rom_index,
+                                        from: rom.boxart_path.clone(),
+                                        to: new_path.clone(),
+                                        moves_boxart_path: true,
+                                    });
+                                    rom.boxart_size = size;
+                                    rom.boxart_has_alpha = None;
+                                }
+                                rom.boxart_path = new_path;
+                            }
+                            Err(e) => {
+                                log::error!("{e}");
+                                state.errors.push(e);
+                            }
+                        }
+                    }
+                    state.push_undo(UndoStep::Renames(renames));
+                }
+                self.recount_stale_boxart_paths();
+            }
+
+            Message::OpenRecentlyModified => {
+                match std::mem::replace(self, NextArtView::default()) {
+                    NextArtView::CollectionList { state, .. } => {
+                        *self = NextArtView::RecentlyModified { state };
+                    }
+                    other => *self = other,
+                }
+            }
+
+            Message::CheckAllArt => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::CollectionList { state, .. } => {
+                    let targets: Vec<(usize, PathBuf)> = state
+                        .index
+                        .listed_roms()
+                        .filter(|(_, rom)| rom.boxart_size != 0)
+                        .map(|(rom_index, rom)| (rom_index, rom.boxart_path.clone()))
+                        .collect();
+                    let read_buffer = state.config.read_buffer_size;
+                    let started = Instant::now();
+                    let total = targets.len();
+                    *self = NextArtView::ArtCheck {
+                        state,
+                        started,
+                        checked: 0,
+                        total,
+                        failures: Vec::new(),
+                    };
+
+                    // One file per blocking thread, as many at a time as there are cores.
+                    let batch_size = std::thread::available_parallelism().map_or(4, usize::from);
+                    return Task::run(
+                        iced::stream::channel(16, move |mut output| async move {
+                            for batch in targets.chunks(batch_size) {
+                                let decodes = batch.iter().map(|(_, path)| {
+                                    let path = path.clone();
+                                    tokio::task::spawn_blocking(move || {
+                                        open_buffered_image(&path, read_buffer).err()
+                                    })
+                                });
+                                let results = iced::futures::future::join_all(decodes).await;
+                                let failures = batch
+                                    .iter()
+                                    .zip(results)
+                                    .filter_map(|((rom_index, _), result)| {
+                                        let error = result.unwrap_or_else(|e| Some(e.to_string()));
+                                        Some((*rom_index, error?))
+                                    })
+                                    .collect();
+                                let _ = output
+                                    .send(Message::ArtCheckProgress(started, batch.len(), failures))
+                                    .await;
+                            }
+                        }),
+                        std::convert::identity,
+                    );
+                }
+                other => *self = other,
+            },
+
+            Message::ArtCheckProgress(run, done, new_failures) => {
+                if let NextArtView::ArtCheck {
+                    started,
+                    checked,
+                    failures,
+                    ..
+                } = self
+                    && *started == run
+                {
+                    *checked += done;
+                    failures.extend(new_failures);
+                }
+            }
+
+            Message::OpenRecentRom(rom_index) => {
+                if let NextArtView::RecentlyModified { state } = self
+                    && let Some(collection) = state.collection_of(rom_index)
+                {
+                    return Task::done(Message::OpenRomList(
+                        collection.name.clone(),
+                        collection.rom_indices.clone(),
+                    ))
+                    .chain(Task::done(Message::SelectRom(rom_index)));
+                }
+            }
+
+            Message::FindOrphanedArt => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    // Notes and kept previous art of existing ROMs count as known files, so only
+                    // orphaned ones are listed.
+                    let boxart_paths: HashSet<PathBuf> = state
+                        .index
+                        .listed_roms()
+                        .flat_map(|(_, x)| {
+                            [x.boxart_path.clone(), x.note_path(), x.previous_art_path()]
+                        })
+                        .collect();
+                    // Only the collections' own media folders: art a gamelist points at can sit next
+                    // to ROMs or other frontends' media, which must never be offered for deletion.
+                    let media_folders: BTreeSet<PathBuf> = state
+                        .index
+                        .collections
+                        .iter()
+                        .map(|x| x.media_folder.clone())
+                        .collect();
+                    return Task::perform(
+                        async move { find_orphaned_art(&media_folders, &boxart_paths) },
+                        Message::FoundOrphanedArt,
+                    );
+                }
+            }
+
+            Message::FoundOrphanedArt(orphans) => {
+                match std::mem::replace(self, NextArtView::default()) {
+                    NextArtView::CollectionList { state, .. } => {
+                        let renames = suggest_art_renames(&orphans, &state.index);
+                        *self = NextArtView::OrphanedArt {
+                            state,
+                            orphans,
+                            renames,
+                            reclaimed: None,
+                        };
+                    }
+                    other => *self = other,
+                }
+            }
+
+            Message::DeleteOrphanedArt => {
+                if let NextArtView::OrphanedArt { orphans, .. } = self {
+                    let orphans: Vec<(PathBuf, u64, WriteTicket)> = orphans
+                        .iter()
+                        .map(|(path, size)| (path.clone(), *size, WriteTicket::reserve(path)))
+                        .collect();
+                    return Task::perform(
+                        async move {
+                            let mut results = Vec::with_capacity(orphans.len());
+                            for (path, size, mut ticket) in orphans {
+                                ticket.ready().await;
+                                let result =
+                                    std::fs::remove_file(&path).map(|()| size).map_err(|e| {
+                                        format!(
+                                            "{}{}': {}",
+                                            strings::ERROR_PREFIX_DELETE_FILE,
+                                            path.display(),
+                                            write_error_detail(&e)
+                                        )
+                                    });
+                                results.push((path, result));
+                            }
+                            results
+                        },
+                        Message::DeletedOrphanedArt,
+                    );
+                }
+            }
+
+            Message::DeletedOrphanedArt(results) => {
+                if let NextArtView::OrphanedArt {
+                    state,
+                    orphans,
+                    renames,
+                    reclaimed,
+                } = self
+                {
+                    let mut reclaimed_bytes = 0;
+                    for (path, result) in results {
+                        match result {
+                            Ok(size) => {
+                                reclaimed_bytes += size;
+                                orphans.retain(|(orphan, _)| *orphan != path);
+                                renames.retain(|(orphan, _)| *orphan != path);
+                            }
+                            Err(e) => state.errors.push(e),
+                        }
+                    }
+                    *reclaimed = Some(reclaimed.unwrap_or(0) + reclaimed_bytes);
+                }
+            }
+
+            Message::RenameOrphanedArt => {
+                if let NextArtView::OrphanedArt { state, renames, .. } = self {
+                    let renames: Vec<(PathBuf, usize, PathBuf, WriteTicket)> = renames
+                        .iter()
+                        .map(|(path, rom_index)| {
+                            let new_path = state.index.roms[*rom_index].boxart_path.clone();
+                            let ticket = WriteTicket::reserve(&new_path);
+                            (path.clone(), *rom_index, new_path, ticket)
+                        })
+                        .collect();
+                    let png_color_type = state.config.png_color_type;
+                    return Task::perform(
+                        async move {
+                            let mut results = Vec::with_capacity(renames.len());
+                            for (path, rom_index, new_path, mut ticket) in renames {
+                                ticket.ready().await;
+                                let result =
+                                    rename_art(&path, &new_path, png_color_type).and_then(|()| {
+                                        std::fs::metadata(&new_path)
+                                            .map(|x| (x.len(), x.modified().ok()))
+                                            .map_err(|e| {
+                                                format!(
+                                                    "{}{}': {}",
+                                                    strings::ERROR_PREFIX_GET_METADATA,
+                                                    new_path.display(),
+                                                    e
+                                                )
+                                            })
+                                    });
+                                results.push((path, rom_index, result));
+                            }
+                            results
+                        },
+                        Message::RenamedOrphanedArt,
+                    );
+                }
+            }
+
+            Message::RenamedOrphanedArt(results) => {
+                if let NextArtView::OrphanedArt {
+                    state,
+                    orphans,
+                    renames,
+                    ..
+                } = self
+                {
+                    let mut art_renames = Vec::new();
+                    for (path, rom_index, result) in results {
+                        match result {
+                            Ok((size, modified)) => {
+                                log::info!(
+                                    "Renamed orphaned art '{}' for ROM {rom_index}",
+                                    path.display()
+                                );
+                                let rom = &mut state.index.roms[rom_index];
+                                rom.boxart_size = size;
+                                rom.boxart_modified = modified;
+                                rom.boxart_has_alpha = None;
+                                rom.boxart_frames = None;
+                                rom.boxart_dimensions = None;
+                                let boxart_path = rom.boxart_path.clone();
+                                state.thumbnails.remove(&boxart_path);
+                                state.mark_rom_written(rom_index);
+                                state.recently_modified.retain(|x| *x != rom_index);
+                                state.recently_modified.push(rom_index);
+                                orphans.retain(|(orphan, _)| *orphan != path);
+                                renames.retain(|(orphan, _)| *orphan != path);
+                                art_renames.push(ArtRename {
+                                    rom_index,
+                                    from: path,
+                                    to: boxart_path,
+                                    moves_boxart_path: false,
+                                });
                             }
+                            Err(e) => {
+                                log::error!("{e}");
+                                state.errors.push(e);
+                            }
+                        }
+                    }
+                    state.push_undo(UndoStep::Renames(art_renames));
+                }
+            }
+
+            Message::SetMediaFolderCreation(media_folder_creation) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.media_folder_creation = media_folder_creation;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetNameNormalization(name_normalization) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.name_normalization = name_normalization;
+                    state.index.normalize_names(name_normalization);
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetDefaultRomAction(default_rom_action) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.default_rom_action = default_rom_action;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetMergeCaseDuplicates(merge) => {
+                if let Some(state) = self.state_mut() {
+                    state.config.merge_case_duplicates = merge;
+                    // Splitting them again needs the folders re-read, so that waits for the next index.
+                    if merge {
+                        state.merge_case_duplicates();
+                    }
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetKeepPreviousArt(keep_previous_art) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.keep_previous_art = keep_previous_art;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetReadArtDimensions(read_art_dimensions) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.read_art_dimensions = read_art_dimensions;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetReadGamelists(read_gamelists) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.read_gamelists = read_gamelists;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetEditorCommand(editor_command) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.editor_command = editor_command;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::CopyRomDebugInfo(rom_index) => {
+                if let NextArtView::RomList {
+                    state,
+                    selected_index,
+                    selected_image,
+                    ..
+                } = self
+                    && let Some(rom) = state.index.roms.get(rom_index)
+                {
+                    // Only known once the preview has been decoded.
+                    let dimensions = match selected_image {
+                        Some(image::Handle::Rgba { width, height, .. })
+                            if *selected_index == Some(rom_index) =>
+                        {
+                            Some((*width, *height))
+                        }
+                        _ => None,
+                    };
+                    let info = serde_json::json!({
+                        "name": rom.name,
+                        "display_name": rom.display_name,
+                        "collection": state.collection_of(rom_index).map(|x| &x.name),
+                        "boxart_path": rom.boxart_path.to_string_lossy(),
+                        "boxart_size": rom.boxart_size,
+                        "dimensions": dimensions,
+                        "format": ImageFormat::from_path(&rom.boxart_path)
+                            .ok()
+                            .map(|x| format!("{x:?}")),
+                        "has_alpha": rom.boxart_has_alpha,
+                        "frames": rom.boxart_frames,
+                    });
+                    return Task::done(Message::SetClipboardText(
+                        serde_json::to_string_pretty(&info).unwrap_or_default(),
+                    ));
+                }
+            }
+
+            Message::SetArtSource(art_source) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.art_source = art_source;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetArtSourceLocation(location) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.art_source_location = location;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::OpenInEditor(rom_index) => {
+                if let NextArtView::RomList {
+                    state,
+                    external_edit,
+                    ..
+                } = self
+                    && let Some(rom) = state.index.roms.get(rom_index)
+                {
+                    let boxart_path = &rom.boxart_path;
+                    if let Err(e) = open_in_editor(boxart_path, &state.config.editor_command) {
+                        state.errors.push(e);
+                        return Task::none();
+                    }
+                    *external_edit = Some(rom_index);
+                }
+            }
+
+            Message::CheckExternalEdit => {
+                if let NextArtView::RomList {
+                    state,
+                    external_edit: Some(rom_index),
+                    ..
+                } = self
+                    && let Some(rom) = state.index.roms.get(*rom_index)
+                {
+                    let rom_index = *rom_index;
+                    let boxart_path = rom.boxart_path.clone();
+                    return Task::perform(
+                        async move { std::fs::metadata(boxart_path) },
+                        move |result| match result {
+                            Ok(metadata) => Message::ExternalEditChecked(
+                                rom_index,
+                                metadata.len(),
+                                metadata.modified().ok(),
+                            ),
+                            // Editors may briefly remove the file while saving.
+                            Err(_) => Message::NoOp,
+                        },
+                    );
+                }
+            }
+
+            Message::ExternalEditChecked(rom_index, size, modified) => {
+                if let NextArtView::RomList {
+                    state,
+                    selected_index,
+                    selected_image,
+                    ..
+                } = self
+                    && let Some(rom) = state.index.roms.get_mut(rom_index)
+                {
+                    if rom.boxart_size == size && rom.boxart_modified == modified {
+                        return Task::none();
+                    }
+                    log::info!("Box art for ROM {rom_index} was changed by an external editor");
+                    rom.boxart_size = size;
+                    rom.boxart_modified = modified;
+                    rom.boxart_has_alpha = None;
+                    rom.boxart_frames = None;
+                    rom.boxart_dimensions = None;
+                    let boxart_path = rom.boxart_path.clone();
+                    state.mark_rom_written(rom_index);
+                    state.recently_modified.retain(|x| *x != rom_index);
+                    state.recently_modified.push(rom_index);
+                    state.thumbnails.remove(&boxart_path);
+                    let thumbnail = if size != 0 && state.config.rom_layout == RomLayout::Gallery {
+                        Self::load_thumbnails_task(vec![boxart_path.clone()], &state.config)
+                    } else {
+                        Task::none()
+                    };
+                    if *selected_index == Some(rom_index) {
+                        *selected_image = None;
+                        return Task::batch([
+                            thumbnail,
+                            Self::load_image_task(boxart_path, state.config.read_buffer_size),
+                        ]);
+                    }
+                    return thumbnail;
+                }
+            }
+
+            Message::SetReadOnly(read_only) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.read_only = read_only;
+                    // `save` skips the write once the setting is on, so write it directly.
+                    CONFIG_SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+                    if let Err(e) = state.config.write() {
+                        state.errors.push(e);
+                    }
+                }
+            }
+
+            Message::SetClipboardAlpha(clipboard_alpha) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.clipboard_alpha = clipboard_alpha;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetFsTimeout(fs_timeout) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.fs_timeout = fs_timeout;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetPreviewMaxSize(preview_max_size) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.preview_max_size = preview_max_size;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetPreviewGuides(preview_guides) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.preview_guides = preview_guides;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::CyclePreviewGuides => {
+                if let Some(state) = self.state_mut() {
+                    state.config.preview_guides = state.config.preview_guides.next();
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetThumbnailCacheLimit(thumbnail_cache_limit) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.thumbnail_cache_limit = thumbnail_cache_limit;
+                    state.thumbnails.evict(thumbnail_cache_limit);
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetPersistThumbnails(persist_thumbnails) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.persist_thumbnails = persist_thumbnails;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::ClearThumbnailCache => {
+                if let Some(state) = self.state_mut() {
+                    state.thumbnails.clear();
+                }
+                return Task::perform(
+                    tokio::task::spawn_blocking(clear_thumbnail_cache),
+                    |result| match result {
+                        Ok(Ok(())) => Message::NoOp,
+                        Ok(Err(e)) => Message::RecordError(e),
+                        Err(e) => Message::RecordError(e.to_string()),
+                    },
+                );
+            }
+
+            Message::SetReadBufferSize(read_buffer_size) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.read_buffer_size = read_buffer_size;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetTheme(theme) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.theme = theme;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetUiScale(ui_scale) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.ui_scale = ui_scale;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetDensity(density) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.density = density;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::RescanCollection => {
+                if let NextArtView::RomList {
+                    state,
+                    title,
+                    rescanning,
+                    ..
+                } = self
+                {
+                    *rescanning = true;
+                    let collection_name = title.clone();
+                    let folder = state.collection_folder(OsStr::new(title));
+                    let folder_name = if state.single_collection {
+                        folder
+                            .file_name()
+                            .map_or_else(|| folder.as_os_str().to_owned(), OsStr::to_owned)
+                    } else {
+                        OsString::from(title.as_str())
+                    };
+                    // Scanned on its own so the errors it finds can be told apart from the rest.
+                    let mut scratch = nextart::State {
+                        index: Index::default(),
+                        errors: Vec::new(),
+                        collection_errors: HashMap::new(),
+                        ..state.library.clone()
+                    };
+                    return Task::perform(
+                        tokio::task::spawn_blocking(move || {
+                            scratch.index_collection(folder, folder_name, &mut |warning| {
+                                log::warn!("{warning}");
+                            });
+                            (collection_name, scratch)
+                        }),
+                        |result| match result {
+                            Ok((collection_name, scratch)) => {
+                                Message::RescannedCollection(collection_name, Box::new(scratch))
+                            }
+                            Err(e) => Message::RecordError(e.to_string()),
+                        },
+                    );
+                }
+            }
+
+            Message::RescannedCollection(collection_name, rescanned) => {
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                let indices = state.merge_rescanned_collection(&collection_name, *rescanned);
+                state.forget_unlisted_roms();
+                log::info!(
+                    "Rescanned collection '{collection_name}': {} ROMs",
+                    indices.len()
+                );
+                for index in &indices {
+                    let boxart_path = &state.library.index.roms[*index].boxart_path;
+                    state.thumbnails.remove(boxart_path);
+                }
+                self.recount_stale_boxart_paths();
+
+                if let NextArtView::RomList {
+                    title, rescanning, ..
+                } = self
+                    && *title == collection_name
+                {
+                    *rescanning = false;
+                    return self.reconcile_rom_list();
+                }
+            }
+
+            Message::Reindex => {
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                if state.reindexing {
+                    return Task::none();
+                }
+                state.reindexing = true;
+                // Built apart so the lists keep working on the current index until it's done.
+                let mut scratch =
+                    nextart::State::new(state.roms_folder.clone(), state.config.clone());
+                scratch.write_count = state.write_count;
+                return Task::perform(
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(e) = scratch.index_roms(&mut |warning| log::warn!("{warning}")) {
+                            scratch.errors.push(e.to_string());
+                        }
+                        scratch
+                    }),
+                    |result| match result {
+                        Ok(scratch) => Message::Reindexed(Box::new(scratch)),
+                        Err(e) => Message::RecordError(e.to_string()),
+                    },
+                );
+            }
+
+            Message::Reindexed(reindexed) => {
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                state.reindexing = false;
+                state.merge_reindexed(*reindexed);
+                state.forget_unlisted_roms();
+                state.thumbnails.clear();
+                log::info!(
+                    "Reindexed {} ROMs in {} collections",
+                    state.index.listed_roms().count(),
+                    state.index.collections.len()
+                );
+                self.recount_stale_boxart_paths();
+                return self.reconcile_rom_list();
+            }
+
+            Message::FindMatchingRoms(rom_index) => {
+                if let NextArtView::RomList {
+                    state,
+                    matching_fill,
+                    ..
+                } = self
+                {
+                    *matching_fill = Some(Box::new((
+                        rom_index,
+                        state.matching_roms_missing_art(rom_index),
+                    )));
+                }
+            }
+
+            Message::ConfirmMatchingFill => {
+                if let NextArtView::RomList {
+                    state,
+                    matching_fill,
+                    ..
+                } = self
+                    && let Some((rom_index, targets)) = matching_fill.take().map(|x| *x)
+                {
+                    let boxart_path = state.index.roms[rom_index].boxart_path.clone();
+                    return self.update(Message::ApplyImageToSelected(
+                        ImageSource::Path(boxart_path),
+                        targets,
+                    ));
+                }
+            }
+
+            Message::CancelMatchingFill => {
+                if let NextArtView::RomList { matching_fill, .. } = self {
+                    *matching_fill = None;
+                }
+            }
+
+            Message::SetArtTransparency(art_transparency) => {
+                if let NextArtView::Settings { state, .. } = self {
+                    state.config.art_transparency = art_transparency;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetRomLayout(rom_layout) => {
+                if let NextArtView::RomList { state, .. } = self {
+                    state.config.rom_layout = rom_layout;
+                    let save = Self::save_config_task(state.config.clone());
+                    return Task::batch([save, self.load_missing_thumbnails()]);
+                }
+            }
+
+            Message::ThumbnailLoaded(boxart_path, handle) => {
+                if let Some(state) = self.state_mut() {
+                    let limit = state.config.thumbnail_cache_limit;
+                    state.thumbnails.insert(boxart_path, handle, limit);
+                }
+            }
+
+            Message::SetOnlyIncompleteCollections(only_incomplete) => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    state.config.only_incomplete_collections = only_incomplete;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetCategoryFilter(category) => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    state.config.category_filter = category;
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetCollectionCategory(collection_name, category) => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    state
+                        .config
+                        .collection_categories
+                        .insert(collection_name, category);
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::ToggleArtExempt(collection_name) => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    let exempt = &mut state.config.art_exempt_collections;
+                    if !exempt.remove(&collection_name) {
+                        exempt.insert(collection_name);
+                    }
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::TogglePinnedCollection(collection_name) => {
+                if let NextArtView::CollectionList { state, .. } = self {
+                    let pinned = &mut state.config.pinned_collections;
+                    if let Some(position) = pinned.iter().position(|x| *x == collection_name) {
+                        pinned.remove(position);
+                    } else {
+                        pinned.push(collection_name);
+                    }
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::StartDraggingCollection(collection_name) => {
+                if let NextArtView::CollectionList { dragging, .. } = self {
+                    *dragging = Some(collection_name);
+                }
+            }
+
+            Message::DragCollectionOver(target) => {
+                if let NextArtView::CollectionList {
+                    state,
+                    dragging: Some(dragged),
+                    ..
+                } = self
+                {
+                    let pinned = &mut state.config.pinned_collections;
+                    if let Some(from) = pinned.iter().position(|x| x == dragged)
+                        && let Some(to) = pinned.iter().position(|x| *x == target)
+                    {
+                        let collection_name = pinned.remove(from);
+                        pinned.insert(to, collection_name);
+                    }
+                }
+            }
+
+            Message::FinishDraggingCollection => {
+                if let NextArtView::CollectionList {
+                    state, dragging, ..
+                } = self
+                    && dragging.take().is_some()
+                {
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SetRomNote(rom_index, note) => {
+                let Some(state) = self.state_mut() else {
+                    return Task::none();
+                };
+                let Some(rom) = state.index.roms.get_mut(rom_index) else {
+                    return Task::none();
+                };
+                rom.note.clone_from(&note);
+                let note_path = rom.note_path();
+                state.mark_rom_written(rom_index);
+                return Self::save_note_task(note_path, note);
+            }
+
+            Message::ToggleVerified(rom_index) => {
+                if let NextArtView::RomList { state, title, .. } = self {
+                    let rom_name = state.index.roms[rom_index].name.clone();
+                    let verified = state.config.verified_roms.entry(title.clone()).or_default();
+                    if !verified.remove(&rom_name) {
+                        verified.insert(rom_name);
+                    }
+                    if verified.is_empty() {
+                        state.config.verified_roms.remove(title);
+                    }
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::SelectNextMissing => {
+                if let NextArtView::RomList {
+                    state,
+                    title,
+                    selected_index,
+                    rom_indices,
+                    filter,
+                    ..
+                } = self
+                {
+                    let visible = Self::visible_roms(state, title, rom_indices, filter);
+                    let start = selected_index
+                        .and_then(|selected| visible.iter().position(|(x, _)| *x == selected))
+                        .map_or(0, |position| position + 1);
+                    let next_missing = visible
+                        .iter()
+                        .cycle()
+                        .skip(start)
+                        .take(visible.len())
+                        .find(|(_, rom)| rom.boxart_size == 0)
+                        .map(|(rom_index, _)| *rom_index);
+
+                    if let Some(rom_index) = next_missing {
+                        return self.update(Message::SelectRom(rom_index));
+                    }
+                }
+            }
+
+            Message::SetVerifiedFilter(verified) => {
+                if let NextArtView::RomList { filter, .. } = self {
+                    filter.verified = verified;
+                }
+            }
+
+            Message::SetRomSearch(search) => {
+                if let NextArtView::RomList { filter, .. } = self {
+                    filter.search = search;
+                }
+            }
+
+            Message::ChooseCollectionDefaultArt(collection_name) => {
+                let start_dir = self.config().last_image_dir;
+                return Task::perform(
+                    async move {
+                        pick_image_file(start_dir.as_deref())
+                            .ok()
+                            .map(|picked| (collection_name, picked))
+                    },
+                    |x| match x {
+                        Some((collection_name, picked)) => Message::ImagePicked(
+                            picked.clone(),
+                            Box::new(Message::SetCollectionDefaultArt(
+                                collection_name,
+                                Some(picked),
+                            )),
+                        ),
+                        None => Message::NoOp,
+                    },
+                );
+            }
+
+            Message::SetCollectionDefaultArt(collection_name, default_art) => {
+                if let Some(state) = self.state_mut() {
+                    let defaults = &mut state.config.collection_default_art;
+                    match default_art {
+                        Some(path) => defaults.insert(collection_name, path),
+                        None => defaults.remove(&collection_name),
+                    };
+                    return Self::save_config_task(state.config.clone());
+                }
+            }
+
+            Message::StartRenamingCollection(collection_name) => {
+                if let NextArtView::CollectionList { renaming, .. } = self {
+                    *renaming = Some((collection_name.clone(), collection_name));
+                    return Task::batch([
+                        text_input::focus(RENAME_COLLECTION_INPUT_ID),
+                        text_input::select_all(RENAME_COLLECTION_INPUT_ID),
+                    ]);
+                }
+            }
+
+            Message::EditCollectionName(name) => {
+                if let NextArtView::CollectionList {
+                    renaming: Some((_, draft)),
+                    ..
+                } = self
+                {
+                    *draft = name;
+                }
+            }
+
+            Message::CancelRenamingCollection => {
+                if let NextArtView::CollectionList { renaming, .. } = self {
+                    *renaming = None;
+                }
+            }
+
+            Message::RenameCollection(old_name, new_name) => {
+                let Some(state) = self.state() else {
+                    return Task::none();
+                };
+                if let Some(error) = state.collection_rename_error(&old_name, &new_name) {
+                    return Task::done(Message::RecordError(error.into()));
+                }
+                if old_name == new_name {
+                    return self.update(Message::CancelRenamingCollection);
+                }
+                return Self::rename_collection_task(
+                    state,
+                    old_name,
+                    new_name,
+                    Message::RenamedCollection,
+                );
+            }
+
+            Message::RenamedCollection(old_name, new_name) => {
+                if let NextArtView::CollectionList { renaming, .. } = self {
+                    *renaming = None;
+                }
+                if let Some(state) = self.state_mut() {
+                    state.push_undo(UndoStep::CollectionRename(
+                        old_name.clone(),
+                        new_name.clone(),
+                    ));
+                }
+                return self.apply_collection_rename(old_name, new_name);
+            }
+
+            Message::RestoredCollectionName(old_name, new_name) => {
+                return self.apply_collection_rename(old_name, new_name);
+            }
+
+            Message::FocusNext => return widget::focus_next(),
+
+            Message::FocusPrevious => return widget::focus_previous(),
+
+            Message::FocusSearch => {
+                if let NextArtView::RomList { .. } = self {
+                    return Task::batch([
+                        text_input::focus(ROM_SEARCH_INPUT_ID),
+                        text_input::select_all(ROM_SEARCH_INPUT_ID),
+                    ]);
+                }
+            }
+
+            Message::ToggleShortcutHelp => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::ShortcutHelp { previous } => *self = *previous,
+                other => {
+                    *self = NextArtView::ShortcutHelp {
+                        previous: Box::new(other),
+                    }
+                }
+            },
+
+            Message::NavigateBack => match self {
+                NextArtView::ShortcutHelp { .. } => {
+                    return self.update(Message::ToggleShortcutHelp);
+                }
+                NextArtView::CollectionList {
+                    renaming: renaming @ Some(_),
+                    ..
+                } => {
+                    *renaming = None;
+                }
+                NextArtView::RomList {
+                    context_menu: context_menu @ Some(_),
+                    ..
+                } => {
+                    *context_menu = None;
+                }
+                NextArtView::RomList { .. }
+                | NextArtView::ErrorList { .. }
+                | NextArtView::Settings { .. }
+                | NextArtView::RecentlyModified { .. }
+                | NextArtView::ArtCheck { .. }
+                | NextArtView::OrphanedArt { .. } => {
+                    return self.update(Message::OpenCollectionList);
+                }
+                NextArtView::CompareArt { .. } => {
+                    return self.update(Message::CancelReplacement);
+                }
+                NextArtView::ConfirmBulkDelete { .. } => {
+                    return self.update(Message::CancelBulkDelete);
+                }
+                NextArtView::ConfirmDiscardMatchingFill { .. } => {
+                    return self.update(Message::KeepMatchingFill);
+                }
+                _ => {}
+            },
+
+            Message::ImagePicked(picked, next) => {
+                if let Some(folder) = picked.parent()
+                    && let Some(state) = self.state_mut()
+                    && state.config.last_image_dir.as_deref() != Some(folder)
+                {
+                    state.config.last_image_dir = Some(folder.to_path_buf());
+                    return Task::batch([
+                        Self::save_config_task(state.config.clone()),
+                        Task::done(*next),
+                    ]);
+                }
+                return Task::done(*next);
+            }
+
+            Message::ContextMenuAction(action) => {
+                if let NextArtView::RomList { context_menu, .. } = self {
+                    *context_menu = None;
+                }
+                return Task::done(*action);
+            }
+
+            Message::CompareArt(pending) => match std::mem::replace(self, NextArtView::default()) {
+                rom_list @ NextArtView::RomList { .. } => {
+                    *self = NextArtView::CompareArt {
+                        previous: Box::new(rom_list),
+                        pending: *pending,
+                    };
+                }
+                other => *self = other,
+            },
+
+            Message::ConfirmReplacement => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::CompareArt { previous, pending } => {
+                    *self = *previous;
+                    let mut save_config = Task::none();
+                    if let NextArtView::RomList { state, saving, .. } = self {
+                        *saving = true;
+                        if state.config.art_adjustment != pending.adjustment {
+                            state.config.art_adjustment = pending.adjustment;
+                            save_config = Self::save_config_task(state.config.clone());
+                        }
+                    }
+                    let rom_index = pending.rom_index;
+                    let config = self.rom_config(rom_index);
+                    let mut ticket = WriteTicket::reserve(&pending.boxart_path);
+                    return Task::batch([
+                        save_config,
+                        Task::perform(
+                            async move {
+                                ticket.ready().await;
+                                write_undoable(rom_index, &pending.boxart_path, || {
+                                    match &pending.source_file {
+                                        Some(source) if pending.adjustment.is_neutral() => {
+                                            import_boxart_file(
+                                                source,
+                                                &pending.boxart_path,
+                                                &config,
+                                            )
+                                        }
+                                        _ => save_boxart(
+                                            pending.adjustment.apply(&pending.replacement),
+                                            &pending.boxart_path,
+                                            &config,
+                                        ),
+                                    }
+                                })
+                            },
+                            |result| match result {
+                                Ok((change, size)) => Message::ArtChanged(change, size),
+                                Err(e) => Message::RecordError(e),
+                            },
+                        ),
+                    ]);
+                }
+                other => *self = other,
+            },
+
+            Message::AdjustCurrentArt(rom_index) => {
+                if let Some(state) = self.state() {
+                    let boxart_path = state.index.roms[rom_index].boxart_path.clone();
+                    let adjustment = state.config.art_adjustment;
+                    return Task::perform(
+                        async move {
+                            let current = open_image(&boxart_path)?;
+                            PendingReplacement::new(
+                                boxart_path,
+                                rom_index,
+                                current,
+                                None,
+                                adjustment,
+                                true,
+                            )
+                        },
+                        |x| match x {
+                            Ok(pending) => Message::CompareArt(Box::new(pending)),
+                            Err(e) => Message::RecordError(e),
+                        },
+                    );
+                }
+            }
+
+            Message::SetArtAdjustment(adjustment) => {
+                if let NextArtView::CompareArt { pending, .. } = self {
+                    pending.set_adjustment(adjustment);
+                }
+            }
+
+            Message::CancelReplacement => match std::mem::replace(self, NextArtView::default()) {
+                NextArtView::CompareArt { previous, .. } => *self = *previous,
+                other => *self = other,
+            },
+
+            Message::ReplaceCurrentImage(source) => {
+                if let NextArtView::RomList {
+                    state,
+                    selected_index: Some(rom_index),
+                    ..
+                } = self
+                {
+                    let boxart_path = state.index.roms[*rom_index].boxart_path.clone();
+                    return Task::done(match source {
+                        ImageSource::Clipboard => {
+                            Message::ReplacementImageFromClip(boxart_path, *rom_index)
+                        }
+                        ImageSource::File => {
+                            Message::ChooseReplacementImage(boxart_path, *rom_index)
+                        }
+                        source @ ImageSource::Path(_) => {
+                            Message::ApplyImageToSelected(source, vec![*rom_index])
+                        }
+                    });
+                }
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Explains an empty collection list, which usually means the wrong folder was picked.
+    fn no_collections_notice<'a>(state: &State) -> Element<'a, Message> {
+        if !state.index.collections.is_empty() {
+            return Space::with_height(0).into();
+        }
+
+        container(
+            column![
+                text(strings::LABEL_NO_COLLECTIONS_FOUND).font(Font {
+                    weight: Weight::Bold,
+                    ..Default::default()
+                }),
+                text!(
+                    "{}{}{}",
+                    strings::LABEL_PREFIX_NO_COLLECTIONS_HINT,
+                    state.roms_folder.display(),
+                    strings::LABEL_SUFFIX_NO_COLLECTIONS_HINT
+                ),
+                button(strings::LABEL_CHOOSE_ANOTHER_FOLDER).on_press(Message::ReturnToSetup),
+            ]
+            .spacing(SPACING_SMALL),
+        )
+        .padding(PADDING_STANDARD)
+        .width(Length::Fill)
+        .style(container::bordered_box)
+        .into()
+    }
+
+    /// Lists orphans that match a ROM that has no art, offering to rename them to that ROM's art.
+    fn orphan_renames<'a>(
+        state: &'a State,
+        renames: &'a [(PathBuf, usize)],
+    ) -> Element<'a, Message> {
+        if renames.is_empty() {
+            return Space::with_height(0).into();
+        }
+
+        column![
+            row![
+                text!(
+                    "{} {}",
+                    renames.len(),
+                    strings::LABEL_ORPHANS_MATCH_RENAMED_ROMS
+                )
+                .width(Length::Fill),
+                write_button(
+                    button(strings::LABEL_RENAME_TO_MATCH),
+                    Some(Message::RenameOrphanedArt),
+                    state.config.is_read_only()
+                ),
+            ]
+            .align_y(Alignment::Center),
+            column(renames.iter().map(|(path, rom_index)| {
+                text!(
+                    "{} → {}",
+                    path.file_name().unwrap_or_default().to_string_lossy(),
+                    state.index.roms[*rom_index]
+                        .boxart_path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                )
+                .into()
+            }))
+            .spacing(SPACING_TINY),
+        ]
+        .spacing(SPACING_SMALL)
+        .into()
+    }
+
+    /// Warns about collections that differ only in case and offers to list them as one.
+    fn case_duplicates_warning<'a>(state: &State) -> Element<'a, Message> {
+        let groups = state.case_duplicate_groups();
+        if groups.is_empty() {
+            return Space::with_height(0).into();
+        }
+
+        row![
+            column(groups.into_iter().map(|group| {
+                text!("{} {}", strings::LABEL_CASE_DUPLICATES, group.join(", "))
+                    .style(text::danger)
+                    .into()
+            }))
+            .width(Length::Fill),
+            button(strings::LABEL_MERGE_CASE_DUPLICATES)
+                .style(button::secondary)
+                .on_press(Message::SetMergeCaseDuplicates(true)),
+        ]
+        .spacing(SPACING_SMALL)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    /// Offers to rename art that was indexed under a previous naming template, if there is any.
+    fn template_migration_row<'a>(state: &State, stale: usize) -> Element<'a, Message> {
+        if stale == 0 {
+            return Space::with_height(0).into();
+        }
+
+        row![
+            text!("{} {}", stale, strings::LABEL_STALE_BOXART_PATHS).width(Length::Fill),
+            write_button(
+                button(strings::LABEL_MIGRATE_BOXART_TEMPLATE),
+                Some(Message::MigrateBoxartTemplate),
+                state.config.is_read_only()
+            ),
+        ]
+        .spacing(SPACING_SMALL)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn adjustment_sliders<'a>(adjustment: ArtAdjustment) -> Element<'a, Message> {
+        let adjustment_row =
+            |label: &'static str, value: i32, set: fn(ArtAdjustment, i32) -> ArtAdjustment| {
+                row![
+                    text(label).width(Length::Fixed(100.0)),
+                    slider(ArtAdjustment::RANGE, value, move |x| {
+                        Message::SetArtAdjustment(set(adjustment, x))
+                    }),
+                    text!("{:+}", value).width(Length::Fixed(50.0)),
+                ]
+                .spacing(SPACING_SMALL)
+                .align_y(Alignment::Center)
+            };
+
+        column![
+            adjustment_row(
+                strings::LABEL_BRIGHTNESS,
+                adjustment.brightness,
+                |x, brightness| { ArtAdjustment { brightness, ..x } }
+            ),
+            adjustment_row(
+                strings::LABEL_CONTRAST,
+                adjustment.contrast,
+                |x, contrast| { ArtAdjustment { contrast, ..x } }
+            ),
+            row![
+                Space::with_width(Length::Fill),
+                button(strings::LABEL_RESET_ADJUSTMENTS)
+                    .style(button::secondary)
+                    .on_press_maybe(
+                        (!adjustment.is_neutral())
+                            .then(|| Message::SetArtAdjustment(ArtAdjustment::default()))
+                    ),
+            ],
+        ]
+        .spacing(SPACING_TINY)
+        .into()
+    }
+
+    fn compare_pane<'a>(
+        label: &'a str,
+        handle: &'a image::Handle,
+        (width, height): (u32, u32),
+    ) -> Element<'a, Message> {
+        column![
+            text(label).font(Font {
+                weight: Weight::Bold,
+                ..Default::default()
+            }),
+            container(image(handle))
+                .center_x(Length::Fill)
+                .height(Length::Fill),
+            text!("{}x{}", width, height),
+        ]
+        .spacing(SPACING_SMALL)
+        .width(Length::FillPortion(1))
+        .align_x(Alignment::Center)
+        .into()
+    }
+
+    fn rename_collection_row<'a>(
+        original: &'a str,
+        draft: &'a str,
+        error: Option<&'static str>,
+    ) -> Element<'a, Message> {
+        let rename = error
+            .is_none()
+            .then(|| Message::RenameCollection(original.to_owned(), draft.to_owned()));
+
+        row![
+            column![
+                text_input(original, draft)
+                    .id(RENAME_COLLECTION_INPUT_ID)
+                    .on_input(Message::EditCollectionName)
+                    .on_submit_maybe(rename.clone()),
+                text(error.unwrap_or("")).style(text::danger),
+            ]
+            .spacing(SPACING_TINY)
+            .width(Length::Fill),
+            button(strings::LABEL_RENAME).on_press_maybe(rename),
+            button(strings::LABEL_CANCEL)
+                .style(button::secondary)
+                .on_press(Message::CancelRenamingCollection),
+        ]
+        .spacing(SPACING_SMALL)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn clipboard_confirmation<'a>(
+        pending: Option<&'a (PathBuf, u32, u32)>,
+    ) -> Element<'a, Message> {
+        let Some((image_path, width, height)) = pending else {
+            return Space::with_height(0).into();
+        };
+
+        row![
+            text!(
+                "{}{}x{}{}",
+                strings::LABEL_PREFIX_LARGE_CLIPBOARD_IMAGE,
+                width,
+                height,
+                strings::LABEL_SUFFIX_LARGE_CLIPBOARD_IMAGE
+            )
+            .width(Length::Fill),
+            button(strings::LABEL_COPY_ANYWAY)
+                .on_press(Message::ConfirmClipboardImage(image_path.clone())),
+            button(strings::LABEL_CANCEL)
+                .style(button::secondary)
+                .on_press(Message::CancelClipboardImage),
+        ]
+        .spacing(SPACING_TINY)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn matching_fill_confirmation<'a>(
+        state: &'a State,
+        pending: Option<&(usize, Vec<usize>)>,
+    ) -> Element<'a, Message> {
+        let Some((_, targets)) = pending else {
+            return Space::with_height(0).into();
+        };
+
+        if targets.is_empty() {
+            return row![
+                text(strings::LABEL_NO_MATCHING_ROMS).width(Length::Fill),
+                button(strings::LABEL_CLOSE)
+                    .style(button::secondary)
+                    .on_press(Message::CancelMatchingFill),
+            ]
+            .spacing(SPACING_TINY)
+            .align_y(Alignment::Center)
+            .into();
+        }
+
+        column![
+            row![
+                text!(
+                    "{} {} {}",
+                    strings::LABEL_PREFIX_MATCHING_ROMS,
+                    targets.len(),
+                    strings::LABEL_SUFFIX_MATCHING_ROMS
+                )
+                .width(Length::Fill),
+                write_button(
+                    button(strings::LABEL_APPLY_TO_MATCHING),
+                    Some(Message::ConfirmMatchingFill),
+                    state.config.is_read_only()
+                ),
+                button(strings::LABEL_CANCEL)
+                    .style(button::secondary)
+                    .on_press(Message::CancelMatchingFill),
+            ]
+            .spacing(SPACING_TINY)
+            .align_y(Alignment::Center),
+            column(targets.iter().filter_map(|x| {
+                let rom = state.index.roms.get(*x)?;
+                Some(
+                    text!(
+                        "{} / {}",
+                        state.collection_of(*x).map_or("", |x| x.name.as_str()),
+                        rom.display_name
+                    )
+                    .into(),
+                )
+            }))
+            .padding(Padding {
+                left: PADDING_SMALL as f32,
+                ..Padding::ZERO
+            }),
+        ]
+        .spacing(SPACING_TINY)
+        .into()
+    }
+
+    /// The collections to list, leaving out complete ones if the user asked to.
+    fn visible_collections<'a>(
+        state: &'a State,
+        renaming: &'a Option<(String, String)>,
+    ) -> impl Iterator<Item = &'a Collection> {
+        let pinned = &state.config.pinned_collections;
+        let by_name = |name: &String| state.index.collections.iter().find(|x| x.name == *name);
+        let unpinned = state
+            .index
+            .collections
+            .iter()
+            .filter(|x| !pinned.contains(&x.name));
+
+        pinned
+            .iter()
+            .filter_map(by_name)
+            .chain(unpinned)
+            .filter(move |x| {
+                !state.config.only_incomplete_collections
+                    || renaming
+                        .as_ref()
+                        .is_some_and(|(original, _)| *original == x.name)
+                    || (!state.is_art_exempt(x) && x.missing_art_count(&state.index.roms) != 0)
+            })
+            .filter(move |x| {
+                let filter = &state.config.category_filter;
+                filter.is_empty()
+                    || renaming
+                        .as_ref()
+                        .is_some_and(|(original, _)| *original == x.name)
+                    || state.config.collection_category(&x.name) == Some(filter.as_str())
+            })
+    }
+
+    /// Tabs for the categories the collections are in, plus one listing every collection.
+    fn category_tabs<'a>(state: &State) -> Element<'a, Message> {
+        let filter = &state.config.category_filter;
+        let mut categories: BTreeSet<&str> = state
+            .index
+            .collections
+            .iter()
+            .filter_map(|x| state.config.collection_category(&x.name))
+            .collect();
+        if categories.is_empty() {
+            return Space::with_height(0).into();
+        }
+        // Kept as a tab even if no collection is in it anymore, so the filter can be seen.
+        if !filter.is_empty() {
+            categories.insert(filter);
+        }
+
+        let tab = |label: &str, category: &str| {
+            button(text(label.to_owned()))
+                .style(if category == filter {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+                .on_press(Message::SetCategoryFilter(category.to_owned()))
+        };
+        row(std::iter::once(tab(strings::LABEL_ALL_CATEGORIES, ""))
+            .chain(categories.into_iter().map(|x| tab(x, x)))
+            .map(Element::from))
+        .spacing(SPACING_TINY)
+        .wrap()
+        .into()
+    }
 
-                            state
-                        },
-                        Message::CompletedIndexing,
-                    );
-                }
-            }
+    fn error_rows<'a>(
+        state: &'a State,
+        category: ErrorCategory,
+        group_by_collection: bool,
+    ) -> Element<'a, Message> {
+        let error_row = |x: &'a String| -> Element<'a, Message> {
+            row![
+                button(strings::LABEL_COPY).on_press(Message::SetClipboardText(x.clone())),
+                text(x)
+            ]
+            .spacing(10)
+            .into()
+        };
+        let errors = state.errors.iter().filter(|x| category.matches(x));
 
-            Message::CompletedIndexing(state) => {
-                *self = NextArtView::CollectionList { state };
-            }
+        if !group_by_collection {
+            return column(errors.map(error_row)).into();
         }
 
-        Task::none()
+        let mut groups: BTreeMap<Option<&str>, Vec<&String>> = BTreeMap::new();
+        for error in errors {
+            groups
+                .entry(state.collection_of_error(error))
+                .or_default()
+                .push(error);
+        }
+        // Errors that aren't about any one collection go last.
+        let unassigned = groups.remove(&None);
+        column(
+            groups
+                .into_iter()
+                .map(|(name, errors)| (name.unwrap_or_default(), errors))
+                .chain(unassigned.map(|x| (strings::LABEL_NOT_IN_A_COLLECTION, x)))
+                .map(|(name, errors)| {
+                    column![text!("{} ({})", name, errors.len()).size(FONT_SIZE_SUBTITLE)]
+                        .extend(errors.into_iter().map(error_row))
+                        .spacing(SPACING_TINY)
+                        .into()
+                }),
+        )
+        .spacing(SPACING_STANDARD)
+        .into()
+    }
+
+    /// The ROMs of a list view in display order, after filtering.
+    fn visible_roms<'a>(
+        state: &'a State,
+        title: &str,
+        rom_indices: &[usize],
+        filter: &RomFilter,
+    ) -> Vec<(usize, &'a Rom)> {
+        let mut roms: Vec<(usize, &Rom)> = rom_indices
+            .iter()
+            .filter_map(|rom_index| Some((*rom_index, state.index.roms.get(*rom_index)?)))
+            .filter(|(_, rom)| filter.matches(rom, state.config.is_verified(title, &rom.name)))
+            .collect();
+        roms.sort_by_key(|x| &x.1.display_name);
+
+        roms
+    }
+
+    fn default_art_bar<'a>(
+        collection_name: &str,
+        default_art: Option<&PathBuf>,
+        missing: Vec<usize>,
+        filtered: bool,
+        read_only: bool,
+    ) -> Element<'a, Message> {
+        let Some(default_art) = default_art else {
+            return row![
+                Space::with_width(Length::Fill),
+                button(strings::LABEL_SET_DEFAULT_ART)
+                    .style(button::secondary)
+                    .on_press(Message::ChooseCollectionDefaultArt(
+                        collection_name.to_owned()
+                    )),
+            ]
+            .into();
+        };
+
+        row![
+            text!(
+                "{} {}",
+                strings::LABEL_DEFAULT_ART,
+                default_art
+                    .file_name()
+                    .unwrap_or(default_art.as_os_str())
+                    .to_string_lossy()
+            )
+            .width(Length::Fill),
+            write_button(
+                button(if filtered {
+                    text!(
+                        "{} ({} {})",
+                        strings::LABEL_FILL_MISSING,
+                        missing.len(),
+                        strings::LABEL_FILTERED
+                    )
+                } else {
+                    text!("{} ({})", strings::LABEL_FILL_MISSING, missing.len())
+                }),
+                (!missing.is_empty()).then(|| {
+                    Message::ApplyImageToSelected(ImageSource::Path(default_art.clone()), missing)
+                }),
+                read_only
+            ),
+            button(strings::LABEL_CHANGE)
+                .style(button::secondary)
+                .on_press(Message::ChooseCollectionDefaultArt(
+                    collection_name.to_owned()
+                )),
+            button(strings::LABEL_CLEAR)
+                .style(button::secondary)
+                .on_press(Message::SetCollectionDefaultArt(
+                    collection_name.to_owned(),
+                    None
+                )),
+        ]
+        .spacing(SPACING_TINY)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn bulk_progress_bar<'a>(bulk_progress: Option<(usize, usize)>) -> Element<'a, Message> {
+        let Some((done, total)) = bulk_progress else {
+            return Space::with_height(0).into();
+        };
+
+        row![
+            text!("{} {}/{}", strings::LABEL_IMPORTING, done, total),
+            progress_bar(0.0..=total.max(1) as f32, done as f32).height(Length::Fixed(8.0)),
+        ]
+        .spacing(SPACING_SMALL)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    /// Actions for the selected ROMs. Selected ROMs the filter hides are left out of them.
+    fn selection_bar<'a>(
+        selected_roms: &BTreeSet<usize>,
+        visible_roms: &[(usize, &Rom)],
+        has_art_source: bool,
+        read_only: bool,
+    ) -> Element<'a, Message> {
+        if selected_roms.is_empty() {
+            return Space::with_height(0).into();
+        }
+
+        let selected: Vec<usize> = visible_roms
+            .iter()
+            .map(|(index, _)| *index)
+            .filter(|x| selected_roms.contains(x))
+            .collect();
+        let hidden = selected_roms.len() - selected.len();
+        let has_selected = !selected.is_empty();
+
+        row![
+            row![text!("{} {}", selected.len(), strings::LABEL_SELECTED)]
+                .push_maybe((hidden != 0).then(|| {
+                    text!("({} {})", hidden, strings::LABEL_HIDDEN_BY_FILTER).font(Font {
+                        weight: Weight::Light,
+                        ..Default::default()
+                    })
+                }))
+                .spacing(SPACING_TINY)
+                .width(Length::Fill),
+            write_button(
+                button(strings::LABEL_APPLY_CLIPBOARD_TO_SELECTED),
+                has_selected.then(|| {
+                    Message::ApplyImageToSelected(ImageSource::Clipboard, selected.clone())
+                }),
+                read_only
+            ),
+            write_button(
+                button(strings::LABEL_APPLY_FILE_TO_SELECTED),
+                has_selected
+                    .then(|| Message::ApplyImageToSelected(ImageSource::File, selected.clone())),
+                read_only
+            ),
+        ]
+        .push_maybe(has_art_source.then(|| {
+            write_button(
+                button(strings::LABEL_FETCH_ART_FOR_SELECTED),
+                has_selected.then(|| Message::FetchArtForSelected(selected.clone())),
+                read_only,
+            )
+        }))
+        .push(write_button(
+            button(strings::LABEL_DELETE_SELECTED_ART).style(danger_button_style),
+            has_selected.then_some(Message::RequestBulkDelete(selected)),
+            read_only,
+        ))
+        .push(
+            button(strings::LABEL_CLEAR_SELECTION)
+                .style(button::secondary)
+                .on_press(Message::ClearRomSelection),
+        )
+        .spacing(SPACING_TINY)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn rom_row<'a>(
+        rom: &'a Rom,
+        rom_index: usize,
+        selected: bool,
+        verified: bool,
+        density: ListDensity,
+        size_units: SizeUnits,
+    ) -> Element<'a, Message> {
+        let name = row![name_text(&rom.display_name, ROM_NAME_MAX_CHARS, |x| x
+            .font(Font {
+                weight: Weight::Bold,
+                ..Default::default()
+            })),]
+        .push_maybe(verified.then(|| text(strings::LABEL_VERIFIED_MARK).style(text::success)))
+        .spacing(SPACING_TINY);
+        let boxart_info = match (rom.boxart_size, rom.boxart_dimensions) {
+            (0, _) => text(strings::LABEL_NO_BOX_ART),
+            (size, Some((width, height))) => text!(
+                "{} {}, {}×{}",
+                ByteSizeFormatter::format_auto(size, size_units.system()),
+                strings::LABEL_BOX_ART,
+                width,
+                height
+            ),
+            (size, None) => text!(
+                "{} {}",
+                ByteSizeFormatter::format_auto(size, size_units.system()),
+                strings::LABEL_BOX_ART
+            ),
+        };
+
+        let selection = checkbox("", selected)
+            .on_toggle(move |selected| Message::ToggleRomSelection(rom_index, selected));
+
+        let content: Element<'a, Message> = match density {
+            ListDensity::Comfortable => row![
+                selection,
+                button(strings::LABEL_MANAGE).on_press(Message::ManageRom(rom_index)),
+                column![name, boxart_info],
+            ]
+            .spacing(SPACING_SMALL)
+            .align_y(Alignment::Center)
+            .into(),
+            ListDensity::Compact => row![
+                selection,
+                button(strings::LABEL_MANAGE)
+                    .padding(PADDING_BUTTON_SMALL)
+                    .on_press(Message::ManageRom(rom_index)),
+                name,
+                boxart_info.font(Font {
+                    weight: Weight::Light,
+                    ..Default::default()
+                }),
+            ]
+            .spacing(SPACING_TINY)
+            .align_y(Alignment::Center)
+            .into(),
+        };
+
+        mouse_area(content)
+            .on_right_press(Message::OpenContextMenu(rom_index))
+            .into()
+    }
+
+    fn gallery_tile<'a>(
+        rom: &'a Rom,
+        rom_index: usize,
+        thumbnail: Option<&image::Handle>,
+        selected: bool,
+        verified: bool,
+    ) -> Element<'a, Message> {
+        let art: Element<'a, Message> = match thumbnail {
+            Some(handle) => image(handle.clone()).into(),
+            None if rom.boxart_size == 0 => text(strings::LABEL_NO_BOX_ART).into(),
+            None => text(strings::LABEL_LOADING_IMAGE).into(),
+        };
+
+        let tile = button(
+            column![
+                container(art).center(GALLERY_THUMBNAIL_SIZE as f32),
+                row![name_text(&rom.display_name, GALLERY_NAME_MAX_CHARS, |x| x
+                    .width(Length::Fill))]
+                .push_maybe(
+                    verified.then(|| text(strings::LABEL_VERIFIED_MARK).style(text::success))
+                ),
+            ]
+            .spacing(SPACING_TINY)
+            .align_x(Alignment::Center),
+        )
+        .width(GALLERY_TILE_WIDTH)
+        .style(if selected {
+            button::primary
+        } else {
+            button::secondary
+        })
+        .on_press(Message::ManageRom(rom_index));
+
+        mouse_area(tile)
+            .on_right_press(Message::OpenContextMenu(rom_index))
+            .into()
     }
 
     fn rom_info_column<'a>(
         rom: &'a Rom,
         rom_index: usize,
         rom_image: &'a Option<image::Handle>,
+        config: &PersistentConfig,
+        verified: bool,
+        saving: bool,
+        clipboard_history: &VecDeque<ClipboardImage>,
     ) -> Element<'a, Message> {
+        let read_only = config.is_read_only();
+        let size_units = config.size_units;
+        let write_action = |label: &'static str, message: Message| {
+            write_button(button(label), (!saving).then_some(message), read_only)
+        };
+
         scrollable(
             column![
-                text(&rom.name)
+                text(&rom.display_name)
                     .font(Font {
                         weight: Weight::Bold,
                         ..Default::default()
                     })
                     .size((FONT_SIZE_TITLE as f32 / 1.5).ceil()),
+                checkbox(strings::LABEL_VERIFIED, verified)
+                    .on_toggle(move |_| Message::ToggleVerified(rom_index)),
+                text_input(strings::LABEL_NOTE_PLACEHOLDER, &rom.note).on_input_maybe(
+                    (!read_only).then_some(move |note| Message::SetRomNote(rom_index, note))
+                ),
                 if rom.boxart_size == 0 {
                     column![
                         text(strings::LABEL_NO_IMAGE).font(Font {
@@ -881,16 +5644,22 @@ impl NextArtView {
                             button(strings::LABEL_COPY_PATH).on_press(Message::SetClipboardText(
                                 rom.boxart_path.to_string_lossy().into()
                             )),
-                            button(strings::LABEL_CHOOSE_IMAGE).on_press(
+                            write_action(
+                                strings::LABEL_CHOOSE_IMAGE,
                                 Message::ChooseReplacementImage(rom.boxart_path.clone(), rom_index)
                             ),
-                            button(strings::LABEL_PASTE_IMAGE).on_press(
+                            write_action(
+                                strings::LABEL_PASTE_IMAGE,
                                 Message::ReplacementImageFromClip(
                                     rom.boxart_path.clone(),
                                     rom_index
                                 )
                             ),
                         ]
+                        .push_maybe(config.art_source().is_some().then(|| write_action(
+                            strings::LABEL_FETCH_ART,
+                            Message::FetchArtForSelected(vec![rom_index])
+                        )))
                         .spacing(SPACING_TINY)
                     ]
                     .width(Length::Fill)
@@ -899,55 +5668,777 @@ impl NextArtView {
                 } else {
                     column![
                         if let Some(handle) = rom_image {
-                            Element::from(image(handle))
+                            // The stack takes the image's size, so the guides line up with it.
+                            let preview = stack![image(handle)]
+                                .push_maybe(preview_guides(config.preview_guides));
+                            match config.preview_max_size {
+                                PreviewSize(0) => Element::from(preview),
+                                PreviewSize(max) => container(preview)
+                                    .max_width(max as f32)
+                                    .max_height(max as f32)
+                                    .into(),
+                            }
                         } else {
                             text(strings::LABEL_LOADING_IMAGE).into()
                         },
+                        text(match rom.boxart_modified {
+                            Some(modified) => format!(
+                                "{}, {} {}",
+                                ByteSizeFormatter::format_auto(
+                                    rom.boxart_size,
+                                    size_units.system()
+                                ),
+                                strings::LABEL_MODIFIED,
+                                format_age(modified)
+                            ),
+                            None => {
+                                ByteSizeFormatter::format_auto(rom.boxart_size, size_units.system())
+                            }
+                        })
+                        .font(Font {
+                            weight: Weight::Light,
+                            ..Default::default()
+                        }),
                         row![
                             button(strings::LABEL_COPY_PATH).on_press(Message::SetClipboardText(
                                 rom.boxart_path.to_string_lossy().into()
                             )),
-                            button(strings::LABEL_CHOOSE_IMAGE).on_press(
+                            write_action(
+                                strings::LABEL_CHOOSE_IMAGE,
                                 Message::ChooseReplacementImage(rom.boxart_path.clone(), rom_index)
                             ),
                             button(strings::LABEL_COPY_IMAGE)
                                 .on_press(Message::SetClipboardImage(rom.boxart_path.clone())),
-                            button(strings::LABEL_PASTE_IMAGE).on_press(
+                            write_action(
+                                strings::LABEL_ADJUST,
+                                Message::AdjustCurrentArt(rom_index)
+                            ),
+                            write_action(
+                                strings::LABEL_OPEN_IN_EDITOR,
+                                Message::OpenInEditor(rom_index)
+                            ),
+                            write_action(
+                                strings::LABEL_COPY_TO_MATCHING,
+                                Message::FindMatchingRoms(rom_index)
+                            ),
+                            write_action(
+                                strings::LABEL_PASTE_IMAGE,
                                 Message::ReplacementImageFromClip(
                                     rom.boxart_path.clone(),
                                     rom_index
                                 )
                             ),
-                            button(strings::LABEL_DELETE)
-                                .on_press(Message::DeleteBoxart(rom.boxart_path.clone(), rom_index))
-                                .style(|theme: &iced::Theme, status| button::Style {
-                                    background: if let button::Status::Hovered = status {
-                                        Some(iced::Background::Color(
-                                            theme.extended_palette().danger.strong.color,
-                                        ))
-                                    } else {
-                                        Some(iced::Background::Color(
-                                            theme.extended_palette().danger.base.color,
-                                        ))
-                                    },
-                                    text_color: theme.palette().text,
-                                    ..Default::default()
-                                }),
+                            write_button(
+                                button(strings::LABEL_DELETE).style(danger_button_style),
+                                (!saving).then(|| Message::DeleteBoxart(
+                                    rom.boxart_path.clone(),
+                                    rom_index
+                                )),
+                                read_only
+                            ),
                         ]
                         .spacing(5)
                     ]
+                    .push_maybe(
+                        rom.boxart_has_alpha
+                            .and_then(|x| config.art_transparency.warning(x))
+                            .map(|x| text(x).style(text::danger)),
+                    )
+                    .push_maybe(rom.boxart_frames.filter(|x| *x > 1).map(|frames| {
+                        text!(
+                            "{} {} {}",
+                            strings::LABEL_PREFIX_ANIMATED_ART,
+                            frames,
+                            strings::LABEL_SUFFIX_ANIMATED_ART
+                        )
+                        .style(text::danger)
+                    }))
                     .width(Length::Fill)
                     .align_x(Alignment::Center)
                     .spacing(10)
                 }
-            ]
-            .align_x(Alignment::Center)
-            .width(Length::Fill),
+            ]
+            .push_maybe((!clipboard_history.is_empty()).then(|| {
+                column![
+                    text(strings::LABEL_RECENT_CLIPBOARD),
+                    row(clipboard_history.iter().map(|x| {
+                        write_button(
+                            button(image(x.thumbnail.clone())).style(button::secondary),
+                            (!saving).then(|| {
+                                Message::ReplacementImageFromHistory(
+                                    rom.boxart_path.clone(),
+                                    rom_index,
+                                    x.clone(),
+                                )
+                            }),
+                            read_only,
+                        )
+                    }))
+                    .spacing(SPACING_TINY)
+                    .wrap(),
+                ]
+                .spacing(SPACING_TINY)
+                .align_x(Alignment::Center)
+            }))
+            .push_maybe(saving.then(|| text(strings::LABEL_SAVING)))
+            .push(
+                button(strings::LABEL_COPY_DEBUG_INFO)
+                    .style(button::secondary)
+                    .on_press(Message::CopyRomDebugInfo(rom_index)),
+            )
+            .align_x(Alignment::Center)
+            .width(Length::Fill),
+        )
+        .into()
+    }
+
+    fn context_menu<'a>(
+        rom: &'a Rom,
+        menu: &ContextMenu,
+        verified: bool,
+        read_only: bool,
+    ) -> Element<'a, Message> {
+        let entry = |label: &'static str| {
+            button(label)
+                .width(Length::Fill)
+                .padding(PADDING_BUTTON_SMALL)
+                .style(button::text)
+        };
+        let action = |label: &'static str, message: Message| {
+            entry(label).on_press(Message::ContextMenuAction(Box::new(message)))
+        };
+        let write_action = |label: &'static str, message: Message| {
+            write_button(
+                entry(label),
+                Some(Message::ContextMenuAction(Box::new(message))),
+                read_only,
+            )
+        };
+
+        let mut entries = column![
+            write_action(
+                strings::LABEL_PASTE_IMAGE,
+                Message::ReplacementImageFromClip(rom.boxart_path.clone(), menu.rom_index)
+            ),
+            write_action(
+                strings::LABEL_CHOOSE_IMAGE,
+                Message::ChooseReplacementImage(rom.boxart_path.clone(), menu.rom_index)
+            ),
+            action(
+                strings::LABEL_COPY_PATH,
+                Message::SetClipboardText(rom.boxart_path.to_string_lossy().into())
+            ),
+            action(
+                if verified {
+                    strings::LABEL_UNMARK_VERIFIED
+                } else {
+                    strings::LABEL_MARK_VERIFIED
+                },
+                Message::ToggleVerified(menu.rom_index)
+            ),
+        ];
+
+        if rom.boxart_size != 0 {
+            entries = entries
+                .push(action(
+                    strings::LABEL_COPY_IMAGE,
+                    Message::SetClipboardImage(rom.boxart_path.clone()),
+                ))
+                .push(write_action(
+                    strings::LABEL_DELETE,
+                    Message::DeleteBoxart(rom.boxart_path.clone(), menu.rom_index),
+                ));
+        }
+
+        container(
+            container(entries.width(CONTEXT_MENU_WIDTH))
+                .padding(SPACING_TINY)
+                .style(container::bordered_box),
+        )
+        .padding(Padding {
+            top: menu.position.y,
+            left: menu.position.x,
+            ..Padding::ZERO
+        })
+        .into()
+    }
+
+    pub fn theme(&self) -> iced::Theme {
+        iced_theme(self.app_theme())
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        let (theme, ui_scale) = self.appearance();
+        theme.scale_factor() * ui_scale.factor()
+    }
+
+    fn app_theme(&self) -> AppTheme {
+        self.appearance().0
+    }
+
+    fn appearance(&self) -> (AppTheme, UiScale) {
+        self.config_ref()
+            .map_or_else(Default::default, |x| (x.theme, x.ui_scale))
+    }
+
+    /// Like `config()`, without cloning the whole config on every frame.
+    fn config_ref(&self) -> Option<&PersistentConfig> {
+        match self {
+            Self::Setup { config, .. } => Some(config),
+            Self::ShortcutHelp { previous } => previous.config_ref(),
+            other => other.state().map(|state| &state.config),
+        }
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let keys = Subscription::batch([
+            keyboard::on_key_press(Self::key_press_message),
+            window::close_requests().map(Message::CloseRequested),
+            match self.state().and_then(|x| x.toast.as_ref()) {
+                Some(_) => iced::time::every(TOAST_POLL_INTERVAL).map(Message::ExpireToast),
+                None => Subscription::none(),
+            },
+        ]);
+
+        match self {
+            Self::ShortcutHelp { previous } => previous.subscription(),
+            Self::RomList {
+                external_edit,
+                resizing_panes,
+                ..
+            } => Subscription::batch([
+                keys,
+                match resizing_panes {
+                    Some(_) => event::listen_with(|event, _status, _window| match event {
+                        Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                            Some(Message::FinishResizingPanes)
+                        }
+                        _ => None,
+                    }),
+                    None => Subscription::none(),
+                },
+                match external_edit {
+                    Some(_) => iced::time::every(EXTERNAL_EDIT_POLL_INTERVAL)
+                        .map(|_| Message::CheckExternalEdit),
+                    None => Subscription::none(),
+                },
+                event::listen_with(|event, _status, _window| match event {
+                    Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                        Some(Message::CursorMoved(position))
+                    }
+                    _ => None,
+                }),
+            ]),
+            Self::CollectionList {
+                dragging: Some(_), ..
+            } => Subscription::batch([
+                keys,
+                event::listen_with(|event, _status, _window| match event {
+                    Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                        Some(Message::FinishDraggingCollection)
+                    }
+                    _ => None,
+                }),
+            ]),
+            _ => keys,
+        }
+    }
+
+    /// Maps key presses that no widget captured (e.g. a focused text input) to messages.
+    fn key_press_message(key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        let key = key.as_ref();
+        SHORTCUTS
+            .iter()
+            .find(|x| x.matches(&key, modifiers))
+            .map(|x| (x.message)())
+    }
+
+    /// Renames a collection's folder, and its folder under the art root if art is kept apart,
+    /// reporting success with `done`.
+    fn rename_collection_task(
+        state: &State,
+        old_name: String,
+        new_name: String,
+        done: fn(String, String) -> Message,
+    ) -> Task<Message> {
+        let old_folder = state.roms_folder.join(&old_name);
+        let new_folder = state.roms_folder.join(&new_name);
+        // With a separate art root the art lives in its own folder, which has to follow.
+        let art_folders = state.config.art_root.as_ref().map(|_| {
+            (
+                state.media_folder(old_name.as_ref()),
+                state.media_folder(new_name.as_ref()),
+            )
+        });
+
+        Task::perform(
+            async move {
+                let rename = |from: &Path, to: &Path| {
+                    std::fs::rename(from, to).map_err(|e| {
+                        format!(
+                            "{}{}' to '{}': {}",
+                            strings::ERROR_PREFIX_RENAME_COLLECTION,
+                            from.display(),
+                            to.display(),
+                            write_error_detail(&e)
+                        )
+                    })
+                };
+
+                let art_folders = art_folders.filter(|(old_art, _)| old_art.exists());
+                if let Some((old_art, new_art)) = &art_folders {
+                    rename(old_art, new_art)?;
+                }
+                if let Err(e) = rename(&old_folder, &new_folder) {
+                    if let Some((old_art, new_art)) = &art_folders {
+                        // Put the art back so it still matches the unrenamed ROM folder.
+                        let _ = std::fs::rename(new_art, old_art);
+                    }
+                    return Err(e);
+                }
+
+                Ok((old_name, new_name))
+            },
+            move |result| match result {
+                Ok((old_name, new_name)) => done(old_name, new_name),
+                Err(e) => Message::RecordError(e),
+            },
+        )
+    }
+
+    /// Updates the index and settings after a collection's folder was renamed.
+    fn apply_collection_rename(&mut self, old_name: String, new_name: String) -> Task<Message> {
+        if let NextArtView::RomList { title, .. } = self
+            && *title == old_name
+        {
+            title.clone_from(&new_name);
+        }
+        let Some(state) = self.state_mut() else {
+            return Task::none();
+        };
+
+        // Art a gamelist points at can be anywhere, so only paths under the renamed
+        // folders move. The media folder is checked first since it's usually inside.
+        let moved_folders = [
+            (
+                state.media_folder(old_name.as_ref()),
+                state.media_folder(new_name.as_ref()),
+            ),
+            (
+                state.collection_folder(old_name.as_ref()),
+                state.collection_folder(new_name.as_ref()),
+            ),
+        ];
+        let index = &mut state.index;
+        if let Some(collection) = index.collections.iter_mut().find(|x| x.name == old_name) {
+            collection.name = new_name.clone();
+            collection.media_folder.clone_from(&moved_folders[0].1);
+            for rom_index in &collection.rom_indices {
+                let rom = &mut index.roms[*rom_index];
+                if let Some(moved) = moved_folders
+                    .iter()
+                    .find_map(|(old, new)| Some(new.join(rom.boxart_path.strip_prefix(old).ok()?)))
+                {
+                    rom.boxart_path = moved;
+                }
+            }
+        }
+
+        if let Some(count) = state.collection_errors.remove(&old_name) {
+            state.collection_errors.insert(new_name.clone(), count);
+        }
+        let config = &mut state.config;
+        if config.art_exempt_collections.remove(&old_name) {
+            config.art_exempt_collections.insert(new_name.clone());
+        }
+        if let Some(pinned) = config
+            .pinned_collections
+            .iter_mut()
+            .find(|x| **x == old_name)
+        {
+            pinned.clone_from(&new_name);
+        }
+        if let Some(default_art) = config.collection_default_art.remove(&old_name) {
+            config
+                .collection_default_art
+                .insert(new_name.clone(), default_art);
+        }
+        if let Some(category) = config.collection_categories.remove(&old_name) {
+            config
+                .collection_categories
+                .insert(new_name.clone(), category);
+        }
+        if let Some(verified) = config.verified_roms.remove(&old_name) {
+            config.verified_roms.insert(new_name, verified);
+        }
+        Self::save_config_task(state.config.clone())
+    }
+
+    /// Takes back (`undo`) or repeats the most recent action on the matching stack.
+    fn restore_history(&mut self, undo: bool) -> Task<Message> {
+        let Some(state) = self.state_mut() else {
+            return Task::none();
+        };
+        let png_color_type = state.config.png_color_type;
+        let (from, to) = if undo {
+            (&mut state.undo_stack, &mut state.redo_stack)
+        } else {
+            (&mut state.redo_stack, &mut state.undo_stack)
+        };
+        let Some(step) = from.pop() else {
+            return Task::none();
+        };
+        // Moved to the other stack right away so repeated presses keep stepping. A collection
+        // rename that's no longer possible is put back below.
+        to.push(step.clone());
+
+        match &step {
+            UndoStep::Art(changes) => {
+                let targets: Vec<(usize, PathBuf, Option<PathBuf>, WriteTicket)> = changes
+                    .iter()
+                    .rev()
+                    .map(|x| {
+                        let version = if undo { &x.before } else { &x.after };
+                        let ticket = WriteTicket::reserve(&x.boxart_path);
+                        (x.rom_index, x.boxart_path.clone(), version.clone(), ticket)
+                    })
+                    .collect();
+                Task::perform(
+                    async move {
+                        let mut results = Vec::with_capacity(targets.len());
+                        for (rom_index, boxart_path, version, mut ticket) in targets {
+                            ticket.ready().await;
+                            results
+                                .push((rom_index, restore_art(&boxart_path, version.as_deref())));
+                        }
+                        results
+                    },
+                    Message::RestoredArt,
+                )
+            }
+            UndoStep::Renames(renames) => {
+                let renames: Vec<(ArtRename, WriteTicket)> = if undo {
+                    renames.iter().rev().cloned().collect::<Vec<_>>()
+                } else {
+                    renames.clone()
+                }
+                .into_iter()
+                .map(|x| {
+                    let ticket = WriteTicket::reserve(if undo { &x.from } else { &x.to });
+                    (x, ticket)
+                })
+                .collect();
+                Task::perform(
+                    async move {
+                        let mut results = Vec::with_capacity(renames.len());
+                        for (rename, mut ticket) in renames {
+                            ticket.ready().await;
+                            let (from, to) = if undo {
+                                (&rename.to, &rename.from)
+                            } else {
+                                (&rename.from, &rename.to)
+                            };
+                            // Orphaned art given back leaves the ROM without art.
+                            let (boxart_path, art) = if rename.moves_boxart_path {
+                                (Some(to.clone()), to)
+                            } else {
+                                (None, &rename.to)
+                            };
+                            let result = rename_art(from, to, png_color_type)
+                                .map(|()| std::fs::metadata(art).map_or(0, |x| x.len()));
+                            results.push((rename.rom_index, boxart_path, result));
+                        }
+                        results
+                    },
+                    Message::RestoredRenames,
+                )
+            }
+            UndoStep::CollectionRename(old_name, new_name) => {
+                let (from, to) = if undo {
+                    (new_name, old_name)
+                } else {
+                    (old_name, new_name)
+                };
+                if let Some(error) = state.collection_rename_error(from, to) {
+                    let (from_stack, to_stack) = if undo {
+                        (&mut state.undo_stack, &mut state.redo_stack)
+                    } else {
+                        (&mut state.redo_stack, &mut state.undo_stack)
+                    };
+                    from_stack.extend(to_stack.pop());
+                    return Task::done(Message::RecordError(error.into()));
+                }
+                Self::rename_collection_task(
+                    state,
+                    from.clone(),
+                    to.clone(),
+                    Message::RestoredCollectionName,
+                )
+            }
+        }
+    }
+
+    /// Marks the ROM info panel as busy; returns false if a write from it is already in flight.
+    fn begin_saving(&mut self) -> bool {
+        match self {
+            NextArtView::RomList { saving, .. } => !std::mem::replace(saving, true),
+            _ => true,
+        }
+    }
+
+    fn state(&self) -> Option<&State> {
+        match self {
+            Self::Loading { state, .. }
+            | Self::CollectionList { state, .. }
+            | Self::RomList { state, .. }
+            | Self::ErrorList { state, .. }
+            | Self::Settings { state, .. }
+            | Self::RecentlyModified { state }
+            | Self::ArtCheck { state, .. }
+            | Self::OrphanedArt { state, .. } => Some(state),
+            Self::CompareArt { previous, .. }
+            | Self::ConfirmBulkDelete { previous, .. }
+            | Self::ConfirmDiscardMatchingFill { previous, .. }
+            | Self::ShortcutHelp { previous } => previous.state(),
+            Self::Setup { .. } | Self::FatalError { .. } => None,
+        }
+    }
+
+    fn state_mut(&mut self) -> Option<&mut State> {
+        match self {
+            Self::Loading { state, .. }
+            | Self::CollectionList { state, .. }
+            | Self::RomList { state, .. }
+            | Self::ErrorList { state, .. }
+            | Self::Settings { state, .. }
+            | Self::RecentlyModified { state }
+            | Self::ArtCheck { state, .. }
+            | Self::OrphanedArt { state, .. } => Some(state),
+            Self::CompareArt { previous, .. }
+            | Self::ConfirmBulkDelete { previous, .. }
+            | Self::ConfirmDiscardMatchingFill { previous, .. }
+            | Self::ShortcutHelp { previous } => previous.state_mut(),
+            Self::Setup { .. } | Self::FatalError { .. } => None,
+        }
+    }
+
+    /// Whether this is a ROM list with matching ROMs waiting for the art to be applied to them.
+    fn has_unapplied_matching_fill(&self) -> bool {
+        matches!(
+            self,
+            Self::RomList {
+                matching_fill: Some(matching_fill),
+                ..
+            } if !matching_fill.1.is_empty()
+        )
+    }
+
+    fn has_boxart(&self, rom_index: usize) -> bool {
+        self.state()
+            .and_then(|state| state.index.roms.get(rom_index))
+            .is_some_and(|rom| rom.boxart_size != 0)
+    }
+
+    fn config(&self) -> PersistentConfig {
+        match self {
+            Self::Setup { config, .. } => config.clone(),
+            Self::ShortcutHelp { previous } => previous.config(),
+            other => other
+                .state()
+                .map(|state| state.config.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn rom_config(&self, rom_index: usize) -> PersistentConfig {
+        match self.state() {
+            Some(state) => state.rom_config(rom_index).into_owned(),
+            None => self.config(),
+        }
+    }
+
+    /// Persists `config` once no other change has come in for a moment, so sliders and rapid
+    /// toggles don't write the file on every step. Call it whenever a persisted field changes.
+    fn save_config_task(config: PersistentConfig) -> Task<Message> {
+        let generation = CONFIG_SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        Task::perform(
+            async move {
+                tokio::time::sleep(CONFIG_SAVE_DELAY).await;
+                if CONFIG_SAVE_GENERATION.load(Ordering::SeqCst) != generation {
+                    return Ok(());
+                }
+                config.save()
+            },
+            |result| match result {
+                Ok(()) => Message::NoOp,
+                Err(e) => Message::RecordError(e),
+            },
+        )
+    }
+
+    /// Writes a ROM's note once typing in it pauses, like `save_config_task`, so the sidecar file
+    /// isn't rewritten on every keystroke.
+    fn save_note_task(note_path: PathBuf, note: String) -> Task<Message> {
+        let generation = NOTE_SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        PENDING_NOTES
+            .lock()
+            .unwrap()
+            .insert(note_path.clone(), (generation, note));
+        Task::perform(
+            async move {
+                tokio::time::sleep(CONFIG_SAVE_DELAY).await;
+                let note = {
+                    let mut pending = PENDING_NOTES.lock().unwrap();
+                    match pending.get(&note_path) {
+                        Some((latest, _)) if *latest == generation => pending.remove(&note_path),
+                        _ => None,
+                    }
+                };
+                let Some((_, note)) = note else {
+                    return Ok(());
+                };
+                let mut ticket = WriteTicket::reserve(&note_path);
+                ticket.ready().await;
+                write_note(&note_path, &note)
+            },
+            |result| match result {
+                Ok(()) => Message::NoOp,
+                Err(e) => Message::RecordError(e),
+            },
+        )
+    }
+
+    /// Updates the Settings view's stale art count, when that's the open view.
+    fn recount_stale_boxart_paths(&mut self) {
+        if let NextArtView::Settings {
+            state,
+            stale_boxart_paths,
+            ..
+        } = self
+        {
+            *stale_boxart_paths = state.stale_boxart_path_count();
+        }
+    }
+
+    /// Brings a ROM list, or the one under a dialog, in line with its collection after the index
+    /// changed. Selections are kept for ROMs that are still listed and dropped for the rest.
+    fn reconcile_rom_list(&mut self) -> Task<Message> {
+        match self {
+            NextArtView::RomList {
+                state,
+                title,
+                rom_indices,
+                selected_index,
+                selected_image,
+                selected_roms,
+                external_edit,
+                matching_fill,
+                context_menu,
+                ..
+            } => {
+                *rom_indices = state
+                    .index
+                    .collections
+                    .iter()
+                    .find(|x| x.name == *title)
+                    .map(|x| x.rom_indices.clone())
+                    .unwrap_or_default();
+                selected_roms.retain(|x| rom_indices.contains(x));
+                if external_edit.is_some_and(|x| !rom_indices.contains(&x)) {
+                    *external_edit = None;
+                }
+                if matching_fill
+                    .as_ref()
+                    .is_some_and(|x| !rom_indices.contains(&x.0))
+                {
+                    *matching_fill = None;
+                }
+                if context_menu
+                    .as_ref()
+                    .is_some_and(|x| !rom_indices.contains(&x.rom_index))
+                {
+                    *context_menu = None;
+                }
+                *selected_image = None;
+                let preview = match selected_index {
+                    Some(index) if !rom_indices.contains(index) => {
+                        *selected_index = None;
+                        Task::none()
+                    }
+                    Some(index) if state.index.roms[*index].boxart_size != 0 => {
+                        Self::load_image_task(
+                            state.index.roms[*index].boxart_path.clone(),
+                            state.config.read_buffer_size,
+                        )
+                    }
+                    _ => Task::none(),
+                };
+                Task::batch([preview, self.load_missing_thumbnails()])
+            }
+            NextArtView::CompareArt { previous, .. }
+            | NextArtView::ConfirmBulkDelete { previous, .. }
+            | NextArtView::ConfirmDiscardMatchingFill { previous, .. }
+            | NextArtView::ShortcutHelp { previous } => previous.reconcile_rom_list(),
+            _ => Task::none(),
+        }
+    }
+
+    /// Loads thumbnails for the open collection's art that isn't cached yet, when shown as a gallery.
+    fn load_missing_thumbnails(&self) -> Task<Message> {
+        let NextArtView::RomList {
+            state, rom_indices, ..
+        } = self
+        else {
+            return Task::none();
+        };
+        if state.config.rom_layout != RomLayout::Gallery {
+            return Task::none();
+        }
+
+        Self::load_thumbnails_task(
+            rom_indices
+                .iter()
+                .filter_map(|x| state.index.roms.get(*x))
+                .filter(|rom| {
+                    rom.boxart_size != 0 && !state.thumbnails.contains_key(&rom.boxart_path)
+                })
+                .map(|rom| rom.boxart_path.clone())
+                .collect(),
+            &state.config,
+        )
+    }
+
+    fn load_thumbnails_task(
+        boxart_paths: Vec<PathBuf>,
+        config: &PersistentConfig,
+    ) -> Task<Message> {
+        if boxart_paths.is_empty() {
+            return Task::none();
+        }
+
+        let read_buffer = config.read_buffer_size;
+        let persist = config.persist_thumbnails && !config.is_read_only();
+
+        Task::run(
+            iced::stream::channel(16, move |mut output| async move {
+                for boxart_path in boxart_paths {
+                    let message = match load_thumbnail(
+                        &boxart_path,
+                        GALLERY_THUMBNAIL_SIZE,
+                        read_buffer,
+                        persist,
+                    ) {
+                        Ok(img) => Message::ThumbnailLoaded(boxart_path, image_handle(&img)),
+                        Err(e) => Message::RecordError(e),
+                    };
+                    let _ = output.send(message).await;
+                }
+            }),
+            std::convert::identity,
         )
-        .into()
     }
 
-    fn load_image_task(image_path: PathBuf) -> Task<Message> {
+    fn load_image_task(image_path: PathBuf, read_buffer: ReadBufferSize) -> Task<Message> {
         Task::perform(
             async move {
                 let file = File::open(&image_path).map_err(|e| {
@@ -959,7 +6450,7 @@ impl NextArtView {
                     )
                 })?;
 
-                let img = ImageReader::new(BufReader::new(file))
+                let reader = ImageReader::new(BufReader::with_capacity(read_buffer.bytes(), file))
                     .with_guessed_format()
                     .map_err(|e| {
                         format!(
@@ -968,85 +6459,762 @@ impl NextArtView {
                             image_path.display(),
                             e
                         )
-                    })?
-                    .decode()
-                    .map_err(|e| {
-                        format!(
-                            "{}{}': {}",
-                            strings::ERROR_PREFIX_DECODE_IMAGE,
-                            image_path.display(),
-                            e
-                        )
                     })?;
+                let format = reader.format();
+                let img = reader.decode().map_err(|e| decode_error(&image_path, e))?;
+
+                // Only the first frame is decoded above, so count the rest separately.
+                let frames =
+                    match format.map(|x| animation_frame_count(&image_path, x, read_buffer)) {
+                        Some(Ok(frames)) => frames,
+                        Some(Err(e)) => {
+                            log::warn!("Failed to count frames of '{}': {e}", image_path.display());
+                            1
+                        }
+                        None => 1,
+                    };
+                if frames > 1 {
+                    log::warn!(
+                        "Box art '{}' is animated with {frames} frames",
+                        image_path.display()
+                    );
+                }
 
-                Ok((img.width(), img.height(), img.to_rgba8().to_vec()))
+                Ok((
+                    img.width(),
+                    img.height(),
+                    img.to_rgba8().to_vec(),
+                    img.color().has_alpha(),
+                    frames,
+                ))
             },
-            |result: Result<(u32, u32, Vec<u8>), String>| match result {
-                Ok((width, height, bytes)) => Message::SetRomInfoImage(width, height, bytes),
+            |result: Result<(u32, u32, Vec<u8>, bool, usize), String>| match result {
+                Ok((width, height, bytes, has_alpha, frames)) => {
+                    Message::SetRomInfoImage(width, height, bytes, has_alpha, frames)
+                }
                 Err(e) => Message::RecordError(e),
             },
         )
     }
 }
 
+fn danger_button_style(theme: &iced::Theme, status: button::Status) -> button::Style {
+    button::Style {
+        background: if let button::Status::Hovered = status {
+            Some(iced::Background::Color(
+                theme.extended_palette().danger.strong.color,
+            ))
+        } else {
+            Some(iced::Background::Color(
+                theme.extended_palette().danger.base.color,
+            ))
+        },
+        text_color: theme.palette().text,
+        ..Default::default()
+    }
+}
+
+static HIGH_CONTRAST_THEME: LazyLock<iced::Theme> = LazyLock::new(|| {
+    iced::Theme::custom(
+        strings::LABEL_THEME_HIGH_CONTRAST.into(),
+        iced::theme::Palette {
+            background: iced::Color::BLACK,
+            text: iced::Color::WHITE,
+            primary: iced::Color::from_rgb8(0xFF, 0xD6, 0x00),
+            success: iced::Color::from_rgb8(0x00, 0xE6, 0x76),
+            danger: iced::Color::from_rgb8(0xFF, 0x52, 0x52),
+        },
+    )
+});
+
+fn iced_theme(theme: AppTheme) -> iced::Theme {
+    match theme {
+        AppTheme::System => iced::Theme::default(),
+        AppTheme::Light => iced::Theme::Light,
+        AppTheme::Dark => iced::Theme::Dark,
+        AppTheme::HighContrast => HIGH_CONTRAST_THEME.clone(),
+    }
+}
+
+fn image_handle(img: &DynamicImage) -> image::Handle {
+    let rgba = img.to_rgba8();
+    image::Handle::from_rgba(rgba.width(), rgba.height(), rgba.into_raw())
+}
+
+fn toast_style(theme: &iced::Theme) -> container::Style {
+    container::Style {
+        background: Some(iced::Background::Color(
+            theme.extended_palette().danger.base.color,
+        )),
+        text_color: Some(theme.extended_palette().danger.base.text),
+        border: iced::Border::default().rounded(4),
+        ..Default::default()
+    }
+}
+
+/// Lines over the box art preview that split it into thirds or mark its center.
+fn preview_guides<'a>(guides: PreviewGuides) -> Option<Element<'a, Message>> {
+    let lines = match guides {
+        PreviewGuides::Off => return None,
+        PreviewGuides::Thirds => 2,
+        PreviewGuides::CenterCross => 1,
+    };
+    let mut vertical = row![Space::with_width(Length::Fill)].height(Length::Fill);
+    let mut horizontal = column![Space::with_height(Length::Fill)].width(Length::Fill);
+    for _ in 0..lines {
+        vertical = vertical
+            .push(vertical_rule(1).style(guide_rule_style))
+            .push(Space::with_width(Length::Fill));
+        horizontal = horizontal
+            .push(horizontal_rule(1).style(guide_rule_style))
+            .push(Space::with_height(Length::Fill));
+    }
+    Some(stack![vertical, horizontal].into())
+}
+
+fn guide_rule_style(theme: &iced::Theme) -> rule::Style {
+    rule::Style {
+        color: theme.extended_palette().primary.strong.color,
+        ..rule::default(theme)
+    }
+}
+
+fn row_spacing(density: ListDensity) -> u16 {
+    match density {
+        ListDensity::Comfortable => SPACING_STANDARD,
+        ListDensity::Compact => SPACING_TINY,
+    }
+}
+
+fn list_padding(density: ListDensity) -> u16 {
+    match density {
+        ListDensity::Comfortable => PADDING_STANDARD,
+        ListDensity::Compact => PADDING_SMALL / 2,
+    }
+}
+
+/// Shortens `name` to `max_chars` with an ellipsis, or returns `None` if it already fits.
+fn truncate_name(name: &str, max_chars: usize) -> Option<String> {
+    if name.chars().count() <= max_chars {
+        return None;
+    }
+    let end = name
+        .char_indices()
+        .nth(max_chars.saturating_sub(1))
+        .map_or(name.len(), |(i, _)| i);
+    Some(format!("{}\u{2026}", name[..end].trim_end()))
+}
+
+/// A name that can't blow out the row it's in: verbose No-Intro style names are cut short, with
+/// the full name in a tooltip. `style` is applied to the text either way.
+fn name_text<'a>(
+    name: &'a str,
+    max_chars: usize,
+    style: impl FnOnce(iced::widget::Text<'a>) -> iced::widget::Text<'a>,
+) -> Element<'a, Message> {
+    let Some(short) = truncate_name(name, max_chars) else {
+        return style(text(name)).into();
+    };
+
+    tooltip(
+        style(text(short)),
+        container(text(name))
+            .padding(SPACING_TINY)
+            .style(container::rounded_box),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// A button that writes to disk: greyed out in read-only mode, with a tooltip saying why.
+fn write_button<'a>(
+    button: iced::widget::Button<'a, Message>,
+    message: Option<Message>,
+    read_only: bool,
+) -> Element<'a, Message> {
+    if !read_only {
+        return button.on_press_maybe(message).into();
+    }
+
+    tooltip(
+        button,
+        container(text(strings::LABEL_READ_ONLY_TOOLTIP))
+            .padding(SPACING_TINY)
+            .style(container::rounded_box),
+        tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// Bumped on every requested config save, so only the most recent one of a burst writes the file.
+static CONFIG_SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Bumped on every note edit, so only the most recent one of a burst writes the note.
+static NOTE_SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Notes waiting to be written by `save_note_task`, keyed by note file, with the generation of the
+/// edit that queued them.
+static PENDING_NOTES: LazyLock<Mutex<HashMap<PathBuf, (u64, String)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Runs `operation` on a fresh clipboard handle, retrying with backoff while another
+/// application briefly holds the clipboard.
+async fn retry_clipboard<T>(
+    error_prefix: &str,
+    operation: impl Fn(&mut Clipboard) -> Result<T, arboard::Error>,
+) -> Result<T, String> {
+    let mut attempt = 1;
+    loop {
+        let error = match Clipboard::new() {
+            Ok(mut clip) => match operation(&mut clip) {
+                Ok(value) => return Ok(value),
+                Err(
+                    e @ (arboard::Error::ContentNotAvailable
+                    | arboard::Error::ClipboardNotSupported
+                    | arboard::Error::ConversionFailure),
+                ) => return Err(format!("{error_prefix}{e}")),
+                Err(e) => format!("{error_prefix}{e}"),
+            },
+            Err(e) => format!("{}{}", strings::ERROR_PREFIX_ACCESS_CLIPBOARD, e),
+        };
+
+        if attempt >= CLIPBOARD_ATTEMPTS {
+            return Err(error);
+        }
+        log::debug!("Clipboard attempt {attempt} failed: {error}");
+        tokio::time::sleep(CLIPBOARD_RETRY_DELAY * attempt).await;
+        attempt += 1;
+    }
+}
+
+/// Copies the image at `image_path` to the clipboard and returns it as decoded.
+async fn copy_image_to_clipboard(
+    image_path: &Path,
+    alpha: ClipboardAlpha,
+) -> Result<DynamicImage, String> {
+    let original = open_image(image_path)?;
+    let mut img = clipboard_rgba(original.clone());
+    if let Some(background) = alpha.background() {
+        flatten_alpha(&mut img, background);
+    }
+    let img_data = ImageData {
+        width: img.width() as usize,
+        height: img.height() as usize,
+        bytes: img.into_raw().into(),
+    };
+
+    retry_clipboard(strings::ERROR_PREFIX_COPY_TO_CLIPBOARD, |clip| {
+        clip.set_image(img_data.clone())
+    })
+    .await
+    .map(|()| original)
+}
+
+/// Converts any decoded image (palette, grayscale, 16-bit, ...) to straight RGBA8 for the clipboard.
+/// Fully transparent pixels are zeroed, so targets that treat the data as premultiplied
+/// don't show stray colour where the image should be empty.
+fn clipboard_rgba(img: DynamicImage) -> RgbaImage {
+    let mut rgba = img.into_rgba8();
+    for pixel in rgba.pixels_mut() {
+        if pixel[3] == 0 {
+            pixel.0 = [0, 0, 0, 0];
+        }
+    }
+    rgba
+}
+
+/// Blends every pixel onto an opaque `background`, leaving the image fully opaque.
+fn flatten_alpha(img: &mut RgbaImage, background: [u8; 3]) {
+    for pixel in img.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        for (channel, background) in pixel.0[..3].iter_mut().zip(background) {
+            *channel =
+                ((*channel as u32 * alpha + background as u32 * (255 - alpha) + 127) / 255) as u8;
+        }
+        pixel[3] = 255;
+    }
+}
+
+/// Describes how long ago `time` was, e.g. "3 days ago".
+fn format_age(time: SystemTime) -> String {
+    let seconds = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or_default()
+        .as_secs();
+
+    let (count, unit) = match seconds {
+        0..60 => return strings::LABEL_JUST_NOW.into(),
+        60..3600 => (seconds / 60, strings::LABEL_UNIT_MINUTE),
+        3600..86400 => (seconds / 3600, strings::LABEL_UNIT_HOUR),
+        86400..2_592_000 => (seconds / 86400, strings::LABEL_UNIT_DAY),
+        2_592_000..31_536_000 => (seconds / 2_592_000, strings::LABEL_UNIT_MONTH),
+        _ => (seconds / 31_536_000, strings::LABEL_UNIT_YEAR),
+    };
+    format!(
+        "{} {}{} {}",
+        count,
+        unit,
+        if count == 1 { "" } else { "s" },
+        strings::LABEL_AGO
+    )
+}
+
+/// Opens the PNG picker, starting in `start_dir` if given.
+fn pick_image_file(start_dir: Option<&Path>) -> Result<PathBuf, String> {
+    let mut dialog = FileDialog::new().add_filter("PNG", &["png"]);
+    if let Some(start_dir) = start_dir {
+        dialog = dialog.set_directory(start_dir);
+    }
+    dialog
+        .pick_file()
+        .ok_or_else(|| strings::ERROR_NO_FILE_SELECTED.into())
+}
+
+async fn read_clipboard_image() -> Result<DynamicImage, String> {
+    let image = match retry_clipboard(strings::ERROR_PREFIX_CLIPBOARD_IMAGE, Clipboard::get_image)
+        .await
+    {
+        Ok(image) => image,
+        // Copying an image address in a browser puts its URL on the clipboard instead of pixels.
+        Err(image_error) => {
+            match retry_clipboard(strings::ERROR_PREFIX_CLIPBOARD_IMAGE, Clipboard::get_text).await
+            {
+                Ok(text) if is_url(text.trim()) => {
+                    let url = text.trim().to_string();
+                    return tokio::task::spawn_blocking(move || download_image(&url))
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+                _ => return Err(image_error),
+            }
+        }
+    };
+    let rgba_image = RgbaImage::from_vec(
+        image.width as u32,
+        image.height as u32,
+        image.bytes.to_vec(),
+    )
+    .ok_or(strings::ERROR_FAILED_CLIPBOARD_IMAGE_OTHER)?;
+
+    Ok(DynamicImage::ImageRgba8(rgba_image))
+}
+
+fn is_url(text: &str) -> bool {
+    (text.starts_with("http://") || text.starts_with("https://"))
+        && !text.contains(char::is_whitespace)
+}
+
+/// Indexes `roms_path` without starting the GUI and prints the index as JSON to stdout.
+fn dump_index(roms_path: PathBuf) -> Result<(), String> {
+    // Read-only so indexing doesn't create media folders in a library that's only being inspected.
+    let config = PersistentConfig {
+        roms_path: roms_path.clone(),
+        read_only: true,
+        ..PersistentConfig::load()?.unwrap_or_default()
+    };
+    let mut state = nextart::State::new(roms_path, config);
+
+    state
+        .index_roms(&mut |warning| eprintln!("{warning}"))
+        .map_err(|e| e.to_string())?;
+    for error in &state.errors {
+        eprintln!("{error}");
+    }
+
+    let serialized = serde_json::to_string_pretty(&state.index).map_err(|e| e.to_string())?;
+    println!("{serialized}");
+
+    Ok(())
+}
+
+/// Enables logging when `NEXTART_LOG` is set, either to a level for NextArt's own messages or to a
+/// full `env_logger` filter. Logs go to stderr, or to the file named by `NEXTART_LOG_FILE`.
+fn init_logging() {
+    let Ok(filter) = std::env::var(LOG_ENV_VAR) else {
+        return;
+    };
+
+    let mut builder = env_logger::Builder::new();
+    if filter.contains(['=', ',']) {
+        builder.parse_filters(&filter);
+    } else {
+        builder.parse_filters(&format!("{}={}", env!("CARGO_CRATE_NAME"), filter));
+    }
+
+    if let Ok(log_path) = std::env::var(LOG_FILE_ENV_VAR) {
+        match File::create(&log_path) {
+            Ok(file) => {
+                builder.target(env_logger::Target::Pipe(Box::new(file)));
+            }
+            Err(e) => eprintln!("{}{}': {}", strings::ERROR_PREFIX_LOG_FILE, log_path, e),
+        }
+    }
+
+    builder.init();
+}
+
 #[tokio::main]
 async fn main() {
+    init_logging();
+
+    if std::env::args().any(|x| x == READ_ONLY_FLAG) {
+        READ_ONLY_LAUNCH.store(true, Ordering::Relaxed);
+    }
+
+    let mut args = std::env::args().skip(1).filter(|x| x != READ_ONLY_FLAG);
+    if let Some("--dump-index") = args.next().as_deref() {
+        let Some(roms_path) = args.next() else {
+            eprintln!("{}", strings::CLI_USAGE_DUMP_INDEX);
+            std::process::exit(2);
+        };
+
+        if let Err(e) = dump_index(PathBuf::from(roms_path)) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     iced::application("NextArt", NextArtView::update, NextArtView::view)
-        .run_with(
-            || match ProjectDirs::from("", strings::DIR_ORG, strings::DIR_APP) {
-                Some(dirs) => {
-                    let mut config_file = dirs.config_dir().to_path_buf();
-                    config_file.push("config.json");
-
-                    match std::fs::read_to_string(&config_file) {
-                        Ok(content) => match serde_json::from_str::<PersistentConfig>(&content) {
-                            Ok(config) => (
-                                NextArtView::Setup {
-                                    chosen_path: Some(config.roms_path),
-                                    error: None,
-                                },
-                                Task::none(),
-                            ),
-                            Err(e) => (
-                                NextArtView::Setup {
-                                    chosen_path: None,
-                                    error: Some(format!(
-                                        "{}: {}",
-                                        strings::ERROR_PREFIX_CONFIG_FILE_READ,
-                                        e
-                                    )),
-                                },
-                                Task::none(),
-                            ),
-                        },
-                        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (
-                            NextArtView::Setup {
-                                chosen_path: None,
-                                error: None,
-                            },
-                            Task::none(),
-                        ),
-                        Err(e) => (
-                            NextArtView::Setup {
-                                chosen_path: None,
-                                error: Some(format!(
-                                    "{}: {}",
-                                    strings::ERROR_PREFIX_CONFIG_FILE_READ,
-                                    e
-                                )),
-                            },
-                            Task::none(),
-                        ),
-                    }
-                }
-                None => (
-                    NextArtView::Setup {
-                        chosen_path: None,
-                        error: Some(strings::ERROR_NO_HOME_DIRECTORY.into()),
-                    },
-                    Task::none(),
-                ),
+        .subscription(NextArtView::subscription)
+        .theme(NextArtView::theme)
+        .scale_factor(NextArtView::scale_factor)
+        .exit_on_close_request(false)
+        .run_with(|| {
+            let (config, error) = match PersistentConfig::load() {
+                Ok(config) => (config, None),
+                Err(e) => (None, Some(e)),
+            };
+
+            (
+                NextArtView::Setup {
+                    chosen_path: config.as_ref().map(|x| x.roms_path.clone()),
+                    config: config.unwrap_or_default(),
+                    error,
+                },
+                Task::none(),
+            )
+        })
+        .expect("Error while running GUI");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_state() -> State {
+        let mut state = State::new(nextart::State::new(
+            PathBuf::from("/roms"),
+            PersistentConfig::default(),
+        ));
+        state.index.collections.push(Collection {
+            name: String::from("GBA"),
+            rom_indices: Vec::new(),
+            manifest: None,
+            no_media: false,
+            media_folder: PathBuf::from("/roms/GBA/.media"),
+        });
+        state.errors.push(String::from("kept across views"));
+        state
+    }
+
+    fn collection_list() -> NextArtView {
+        NextArtView::CollectionList {
+            state: test_state(),
+            renaming: None,
+            dragging: None,
+        }
+    }
+
+    fn navigate(mut view: NextArtView, message: Message) -> NextArtView {
+        let _ = view.update(message);
+        view
+    }
+
+    fn assert_state_kept(view: &NextArtView) {
+        let state = view.state().expect("view should hold the state");
+        assert_eq!(state.roms_folder, PathBuf::from("/roms"));
+        assert_eq!(state.errors, vec![String::from("kept across views")]);
+        assert_eq!(state.index.collections.len(), 1);
+    }
+
+    #[test]
+    fn collection_list_opens_rom_list() {
+        let view = navigate(
+            collection_list(),
+            Message::OpenRomList(String::from("GBA"), Vec::new()),
+        );
+
+        assert!(matches!(&view, NextArtView::RomList { title, .. } if title == "GBA"));
+        assert_state_kept(&view);
+    }
+
+    #[test]
+    fn rom_list_returns_to_collection_list() {
+        let view = navigate(
+            collection_list(),
+            Message::OpenRomList(String::from("GBA"), Vec::new()),
+        );
+        let view = navigate(view, Message::OpenCollectionList);
+
+        assert!(matches!(view, NextArtView::CollectionList { .. }));
+        assert_state_kept(&view);
+    }
+
+    #[test]
+    fn error_list_opens_from_lists_and_returns() {
+        let view = navigate(collection_list(), Message::OpenErrorList);
+        assert!(matches!(view, NextArtView::ErrorList { .. }));
+        assert_state_kept(&view);
+
+        let view = navigate(
+            navigate(view, Message::OpenRomList(String::from("GBA"), Vec::new())),
+            Message::OpenErrorList,
+        );
+        assert!(matches!(view, NextArtView::ErrorList { .. }));
+        assert_state_kept(&view);
+
+        let view = navigate(view, Message::OpenCollectionList);
+        assert!(matches!(view, NextArtView::CollectionList { .. }));
+        assert_state_kept(&view);
+    }
+
+    #[test]
+    fn secondary_views_return_to_collection_list() {
+        let views = [
+            NextArtView::Settings {
+                state: test_state(),
+                boxart_template: String::new(),
+                excluded_collections: String::new(),
+                extension_systems: String::new(),
+                system_categories: String::new(),
+                stale_boxart_paths: 0,
+            },
+            NextArtView::RecentlyModified {
+                state: test_state(),
             },
+            NextArtView::ArtCheck {
+                state: test_state(),
+                started: Instant::now(),
+                checked: 0,
+                total: 0,
+                failures: Vec::new(),
+            },
+            NextArtView::OrphanedArt {
+                state: test_state(),
+                orphans: Vec::new(),
+                renames: Vec::new(),
+                reclaimed: None,
+            },
+        ];
+
+        for view in views {
+            let view = navigate(view, Message::OpenCollectionList);
+            assert!(matches!(view, NextArtView::CollectionList { .. }));
+            assert_state_kept(&view);
+        }
+    }
+
+    #[test]
+    fn leaving_an_unapplied_matching_fill_asks_first() {
+        let mut view = navigate(
+            collection_list(),
+            Message::OpenRomList(String::from("GBA"), Vec::new()),
+        );
+        if let NextArtView::RomList { matching_fill, .. } = &mut view {
+            *matching_fill = Some(Box::new((0, vec![1, 2])));
+        }
+
+        let view = navigate(view, Message::OpenCollectionList);
+        assert!(matches!(
+            view,
+            NextArtView::ConfirmDiscardMatchingFill { .. }
+        ));
+
+        let view = navigate(view, Message::KeepMatchingFill);
+        assert!(matches!(
+            view,
+            NextArtView::RomList {
+                matching_fill: Some(_),
+                ..
+            }
+        ));
+
+        let view = navigate(
+            navigate(view, Message::OpenErrorList),
+            Message::DiscardMatchingFill,
+        );
+        assert!(matches!(view, NextArtView::ErrorList { .. }));
+        assert_state_kept(&view);
+    }
+
+    fn test_rom(name: &str) -> Rom {
+        Rom::new(
+            OsStr::new(name),
+            String::from(name),
+            PathBuf::from(format!("/roms/GBA/.media/{name}.png")),
         )
-        .expect("Error while running GUI");
+    }
+
+    #[test]
+    fn reindex_keeps_selections_by_name() {
+        let mut state = test_state();
+        state.index.roms = ["a", "b", "c"].map(test_rom).into();
+        state.index.collections[0].rom_indices = vec![0, 1, 2];
+        let mut view = NextArtView::CollectionList {
+            state,
+            renaming: None,
+            dragging: None,
+        };
+        view = navigate(
+            view,
+            Message::OpenRomList(String::from("GBA"), vec![0, 1, 2]),
+        );
+        view = navigate(view, Message::SelectRoms(vec![0, 2]));
+        view = navigate(view, Message::SelectRom(2));
+
+        // Reindexed with "a" gone and "d" added, in a different order.
+        let mut reindexed = test_state();
+        reindexed.index.roms = ["d", "c", "b"].map(test_rom).into();
+        reindexed.index.collections[0].rom_indices = vec![0, 1, 2];
+        let view = navigate(view, Message::Reindexed(Box::new(reindexed.library)));
+
+        let NextArtView::RomList {
+            state,
+            rom_indices,
+            selected_index,
+            selected_roms,
+            ..
+        } = &view
+        else {
+            panic!("reindexing should stay in the ROM list");
+        };
+        let names = |indices: &mut dyn Iterator<Item = &usize>| {
+            indices
+                .map(|x| state.index.roms[*x].name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&mut rom_indices.iter()), ["d", "c", "b"]);
+        assert_eq!(names(&mut selected_index.iter()), ["c"]);
+        assert_eq!(names(&mut selected_roms.iter()), ["c"]);
+        assert!(!state.reindexing);
+    }
+
+    #[test]
+    fn invalid_navigation_keeps_the_current_view() {
+        let setup = NextArtView::Setup {
+            chosen_path: Some(PathBuf::from("/roms")),
+            config: PersistentConfig::default(),
+            error: None,
+        };
+        for message in [
+            Message::OpenCollectionList,
+            Message::OpenErrorList,
+            Message::OpenRomList(String::from("GBA"), Vec::new()),
+        ] {
+            let view = navigate(setup.clone(), message);
+            assert!(matches!(
+                view,
+                NextArtView::Setup { chosen_path: Some(ref path), .. } if path == Path::new("/roms")
+            ));
+        }
+
+        let view = navigate(
+            NextArtView::Settings {
+                state: test_state(),
+                boxart_template: String::from("{stem}.jpg"),
+                excluded_collections: String::new(),
+                extension_systems: String::new(),
+                system_categories: String::new(),
+                stale_boxart_paths: 0,
+            },
+            Message::OpenErrorList,
+        );
+        assert!(matches!(
+            &view,
+            NextArtView::Settings { boxart_template, .. } if boxart_template == "{stem}.jpg"
+        ));
+        assert_state_kept(&view);
+
+        let view = navigate(
+            NextArtView::Loading {
+                state: test_state(),
+                message: String::new(),
+            },
+            Message::OpenCollectionList,
+        );
+        assert!(matches!(view, NextArtView::Loading { .. }));
+        assert_state_kept(&view);
+    }
+
+    #[test]
+    fn palette_png_expands_to_rgba() {
+        let palette = [255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30];
+        let transparency = [255, 128, 255, 0];
+        let indices = [0u8, 1, 2, 3];
+
+        let mut encoded = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut encoded, 2, 2);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(&palette[..]);
+            encoder.set_trns(&transparency[..]);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&indices).unwrap();
+        }
+
+        let decoded = ::image::load_from_memory_with_format(&encoded, ImageFormat::Png).unwrap();
+        let rgba = clipboard_rgba(decoded);
+
+        assert_eq!(rgba.dimensions(), (2, 2));
+        assert_eq!(
+            rgba.into_raw(),
+            vec![
+                255, 0, 0, 255, //
+                0, 255, 0, 128, //
+                0, 0, 255, 255, //
+                0, 0, 0, 0,
+            ]
+        );
+    }
+
+    #[test]
+    fn collection_renames_can_be_undone() {
+        let view = navigate(
+            collection_list(),
+            Message::RenamedCollection(String::from("GBA"), String::from("Advance")),
+        );
+        let state = view.state().unwrap();
+        assert_eq!(state.index.collections[0].name, "Advance");
+        assert_eq!(state.undo_stack.len(), 1);
+
+        // The old name was taken in the meantime, so there's nothing to undo into yet.
+        let mut view = view;
+        view.state_mut()
+            .unwrap()
+            .index
+            .collections
+            .push(Collection {
+                name: String::from("GBA"),
+                rom_indices: Vec::new(),
+                manifest: None,
+                no_media: false,
+                media_folder: PathBuf::from("/roms/GBA/.media"),
+            });
+        let mut view = navigate(view, Message::Undo);
+        let state = view.state_mut().unwrap();
+        assert_eq!((state.undo_stack.len(), state.redo_stack.len()), (1, 0));
+
+        state.index.collections.pop();
+        let view = navigate(view, Message::Undo);
+        let state = view.state().unwrap();
+        assert_eq!((state.undo_stack.len(), state.redo_stack.len()), (0, 1));
+        let view = navigate(
+            view,
+            Message::RestoredCollectionName(String::from("Advance"), String::from("GBA")),
+        );
+        let state = view.state().unwrap();
+        assert_eq!(state.index.collections[0].name, "GBA");
+        assert_eq!(state.redo_stack.len(), 1);
+    }
 }
@@ -1,65 +1,393 @@
 pub const ERROR_CANNOT_NAVIGATE: &str = "Cannot navigate: No state available";
-pub const ERROR_NO_FILE_SELECTED: &str = "No file selected";
-pub const ERROR_NO_PATH: &str = "No path selected.";
 pub const ERROR_CANNOT_NAVIGATE_COLLECTIONS: &str =
     "Cannot navigate to collections: Current view doesn't contain a valid state";
-pub const ERROR_PREFIX_ACCESS_CLIPBOARD: &str = "Failed to access clipboard: ";
-pub const ERROR_PREFIX_CLIPBOARD_IMAGE: &str = "Failed to get image from clipboard: ";
+pub const ERROR_COLLECTION_EXISTS: &str = "A collection with this name already exists.";
 pub const ERROR_FAILED_CLIPBOARD_IMAGE_OTHER: &str =
     "Failed to create image from clipboard data: Invalid image dimensions or data.";
+pub const ERROR_FS_TIMED_OUT: &str =
+    "No response from the filesystem within the configured timeout";
+pub const ERROR_INFIX_ART_NOT_IN_SOURCE: &str = "' in the art source folder '";
+pub const ERROR_INVALID_BOXART_TEMPLATE: &str = "The name must contain {stem}, end in an image extension like .png, and cannot contain path separators or '..'.";
+pub const ERROR_INVALID_COLLECTION_NAME: &str =
+    "Collection names can't be empty, start with '.', or contain < > : \" / \\ | ? *";
+pub const ERROR_INVALID_MAX_ART_SIZE: &str = "max_art_size must be larger than zero.";
+pub const ERROR_NO_ART_SOURCE: &str = "No art source is set up. Choose one in Settings.";
+pub const ERROR_NO_FILE_SELECTED: &str = "No file selected";
 pub const ERROR_NO_HOME_DIRECTORY: &str = "Failed to retrieve home directory from operating system. Roms path will not be pre-filled on restart.";
+pub const ERROR_NO_PATH: &str = "No path selected.";
+pub const ERROR_PREFIX_ACCESS_CLIPBOARD: &str = "Failed to access clipboard: ";
+pub const ERROR_PREFIX_ART_NOT_IN_SOURCE: &str = "No art for '";
+pub const ERROR_PREFIX_BACKUP_ART: &str = "Failed to keep an undo copy of '";
+pub const ERROR_PREFIX_BOXART_EXISTS: &str = "Did not rename box art to '";
+pub const ERROR_PREFIX_CLEAR_THUMBNAIL_CACHE: &str = "Failed to clear the thumbnail cache in '";
+pub const ERROR_PREFIX_CLIPBOARD_IMAGE: &str = "Failed to get image from clipboard: ";
 pub const ERROR_PREFIX_CONFIG_DIR_CREATE: &str =
     "Failed to create config directory. Roms path will not be pre-filled on restart";
 pub const ERROR_PREFIX_CONFIG_FILE_CREATE: &str =
     "Failed to create config file. Roms path will not be pre-filled on restart";
 pub const ERROR_PREFIX_CONFIG_FILE_READ: &str = "Failed to read config file";
-pub const ERROR_PREFIX_DELETE_FILE: &str = "Failed to delete file '";
+pub const ERROR_PREFIX_CONFIG_TOML_READ: &str = "Failed to read config.toml";
 pub const ERROR_PREFIX_COPY_FILE: &str = "Failed to copy file from '";
+pub const ERROR_PREFIX_COPY_TO_CLIPBOARD: &str = "Failed to copy image to clipboard: ";
+pub const ERROR_PREFIX_COVERAGE_FILE_CREATE: &str =
+    "Failed to save art coverage. Progress since this session will not be shown on restart";
+pub const ERROR_PREFIX_COVERAGE_FILE_READ: &str = "Failed to read art coverage from last session";
 pub const ERROR_PREFIX_DECODE_IMAGE: &str = "Failed to decode image '";
+pub const ERROR_PREFIX_DELETE_FILE: &str = "Failed to delete file '";
 pub const ERROR_PREFIX_DIR_ENTRY: &str = "Failed to read directory entry: ";
 pub const ERROR_PREFIX_DIR_READ: &str = "Failed to read directory '";
 pub const ERROR_PREFIX_DIR_TYPE: &str = "Failed to determine file type for '";
+pub const ERROR_PREFIX_DOWNLOAD: &str = "Failed to download '";
 pub const ERROR_PREFIX_FILE_STEM: &str = "Failed to extract file stem: ";
 pub const ERROR_PREFIX_GET_METADATA: &str = "Failed to get metadata for '";
 pub const ERROR_PREFIX_GET_METADATA_SAVED: &str = "Failed to get metadata for saved image '";
 pub const ERROR_PREFIX_GUESS_FORMAT: &str = "Failed to guess format for '";
+pub const ERROR_PREFIX_IMPORTED_ART_ROOT: &str = "The imported separate art folder '";
+pub const ERROR_PREFIX_IMPORTED_ROMS_PATH: &str = "The imported Roms folder '";
+pub const ERROR_PREFIX_IMPORT_JOURNAL_READ: &str =
+    "Failed to read the progress of an interrupted import. It will start over";
+pub const ERROR_PREFIX_IMPORT_JOURNAL_WRITE: &str =
+    "Failed to save import progress. An interrupted import will start over";
 pub const ERROR_PREFIX_INDEX_COLLECTION: &str = "Failed to index collection '";
+pub const ERROR_PREFIX_KEEP_PREVIOUS_ART: &str = "Failed to keep the replaced art as '";
+pub const ERROR_PREFIX_LOG_FILE: &str = "Failed to create log file '";
+pub const ERROR_PREFIX_LOSSY_FILE_NAME: &str =
+    "File name is not valid UTF-8 and will be displayed approximately: '";
 pub const ERROR_PREFIX_MEDIA_DIR: &str = "Failed to create media folder '";
+pub const ERROR_PREFIX_MEDIA_NOT_DIR: &str = "Expected a media folder but found a file at '";
+pub const ERROR_PREFIX_OPEN_EDITOR: &str = "Failed to open an editor for '";
 pub const ERROR_PREFIX_OPEN_IMAGE: &str = "Failed to open image '";
 pub const ERROR_PREFIX_OPEN_IMAGE_FILE: &str = "Failed to open image file '";
+pub const ERROR_PREFIX_READ_ART_SOURCE: &str = "Failed to read art source image '";
 pub const ERROR_PREFIX_READ_COLLECTION: &str = "Failed to read collection directory '";
+pub const ERROR_PREFIX_READ_DIMENSIONS: &str = "Failed to read the dimensions of '";
+pub const ERROR_PREFIX_READ_GAMELIST: &str = "Ignored unreadable gamelist '";
+pub const ERROR_PREFIX_READ_MANIFEST: &str = "Ignored invalid collection manifest '";
+pub const ERROR_PREFIX_READ_NOTE: &str = "Failed to read note '";
+pub const ERROR_PREFIX_RENAME_BOXART: &str = "Failed to rename box art '";
+pub const ERROR_PREFIX_RENAME_COLLECTION: &str = "Failed to rename collection folder '";
+pub const ERROR_PREFIX_RESTORE_ART: &str = "Failed to restore box art '";
+pub const ERROR_PREFIX_ROMS_PATH_NOT_DIR: &str = "The Roms path '";
 pub const ERROR_PREFIX_SAVE_IMAGE: &str = "Failed to save image to '";
-pub const ERROR_PREFIX_COPY_TO_CLIPBOARD: &str = "Failed to copy image to clipboard: ";
+pub const ERROR_PREFIX_SAVE_NOTE: &str = "Failed to save note '";
+pub const ERROR_PREFIX_SETTINGS_EXPORT: &str = "Failed to export settings";
+pub const ERROR_PREFIX_SETTINGS_IMPORT: &str = "Failed to import settings";
+pub const ERROR_PREFIX_UNSUPPORTED_FORMAT: &str = "Cannot read image '";
+pub const ERROR_PREFIX_URL_NOT_IMAGE: &str = "The copied address '";
+pub const ERROR_PREFIX_WRITES_PENDING: &str = "Did not close because ";
+pub const ERROR_READ_ONLY: &str =
+    "Read-only mode is on, so nothing was written. Turn it off in Settings to make changes.";
+pub const ERROR_SUFFIX_BOXART_EXISTS: &str = " because a file with that name already exists.";
+pub const ERROR_SUFFIX_IMPORTED_ART_ROOT: &str =
+    " does not exist on this machine and was cleared. Pick it again if you use one.";
+pub const ERROR_SUFFIX_IMPORTED_ROMS_PATH: &str =
+    " does not exist on this machine. Pick where the Roms folder is here.";
+pub const ERROR_SUFFIX_MEDIA_NOT_DIR: &str =
+    ". Rename or remove this file so box art can be stored for this collection.";
+pub const ERROR_SUFFIX_PERMISSION_DENIED: &str = ". The folder can't be written to: check that the SD card isn't mounted read-only or locked, and that your user has permission to change its files.";
+pub const ERROR_SUFFIX_ROMS_PATH_NOT_DIR: &str =
+    " is a file, not a folder. Choose the folder that contains your collection folders.";
+pub const ERROR_SUFFIX_UNKNOWN_FORMAT: &str =
+    ": the image format was not recognized. Convert the image to PNG and try again.";
+pub const ERROR_SUFFIX_UNSUPPORTED_FORMAT: &str =
+    " images are not supported by this build of NextArt. Convert the image to PNG and try again.";
+pub const ERROR_SUFFIX_URL_NOT_IMAGE: &str = " does not point to an image. The server sent: ";
+pub const ERROR_SUFFIX_WRITES_PENDING: &str = " box art writes are still running. Close the window again to quit anyway, which may leave half-written files.";
 
+pub const LABEL_ADJUST: &str = "Adjust";
+pub const LABEL_AGO: &str = "ago";
+pub const LABEL_ALL_CATEGORIES: &str = "All";
+pub const LABEL_APPLY_CLIPBOARD_TO_SELECTED: &str = "Paste Image to Selected";
+pub const LABEL_APPLY_FILE_TO_SELECTED: &str = "Choose Image for Selected";
+pub const LABEL_APPLY_TO_MATCHING: &str = "Apply";
+pub const LABEL_ART_ADDED_SINCE_LAST: &str = "art added since last time";
+pub const LABEL_ART_CHECK_EXPLANATION: &str = "Every box art file is decoded the way the device would read it. Art listed here is corrupt or in a format the device can't show; replace it before copying the library to your card.";
+pub const LABEL_ART_FILES_BROKEN: &str = "could not be decoded";
+pub const LABEL_ART_FILES_CHECKED: &str = "box art files checked";
+pub const LABEL_ART_HAS_ALPHA: &str =
+    "This art has an alpha channel, but the frontend shows art without a background behind it.";
+pub const LABEL_ART_LOST_SINCE_LAST: &str = "art went missing since last time";
+pub const LABEL_ART_NOT_EXPECTED: &str = "box art not expected";
+pub const LABEL_ART_NO_ALPHA: &str =
+    "This art has no alpha channel, so it will cover the background the frontend draws behind it.";
+pub const LABEL_ART_ROOT: &str = "Separate art folder (optional)";
+pub const LABEL_ART_ROOT_HINT: &str = "To keep box art outside the Roms folder, pick a folder that will hold one subfolder per collection.";
+pub const LABEL_ART_SOURCE: &str = "Art source";
+pub const LABEL_ART_SOURCE_FOLDER_PLACEHOLDER: &str = "/path/to/art";
+pub const LABEL_ART_SOURCE_HINT: &str = "Where Fetch Art looks up art by ROM name: a folder of images named after the ROMs, or a URL with {name} where the ROM name goes.";
+pub const LABEL_ART_SOURCE_HTTP: &str = "HTTP";
+pub const LABEL_ART_SOURCE_LOCAL_FOLDER: &str = "Local folder";
+pub const LABEL_ART_SOURCE_NONE: &str = "None";
+pub const LABEL_ART_SOURCE_URL_PLACEHOLDER: &str = "https://example.com/art/{name}.png";
+pub const LABEL_ART_TRANSPARENCY: &str = "Frontend expects box art to be";
 pub const LABEL_BACK: &str = "Back";
+pub const LABEL_BOXART_TEMPLATE: &str = "Box art file name";
+pub const LABEL_BOXART_TEMPLATE_HINT: &str =
+    "{stem} is replaced by the ROM's file name. Applies the next time the library is indexed.";
 pub const LABEL_BOX_ART: &str = "Box Art";
+pub const LABEL_BRIGHTNESS: &str = "Brightness";
+pub const LABEL_CANCEL: &str = "Cancel";
+pub const LABEL_CASE_DUPLICATES: &str =
+    "These folders differ only in upper/lower case and may be the same folder on some systems:";
+pub const LABEL_CATEGORY_PLACEHOLDER: &str = "Category";
+pub const LABEL_CHANGE: &str = "Change";
+pub const LABEL_CHECKING_ART: &str = "Decoding box art";
+pub const LABEL_CHECK_ALL_ART: &str = "Check All Art";
+pub const LABEL_CHOOSE_ANOTHER_FOLDER: &str = "Choose Another Folder";
 pub const LABEL_CHOOSE_IMAGE: &str = "Choose Image";
+pub const LABEL_CLEAR: &str = "Clear";
+pub const LABEL_CLEAR_SELECTION: &str = "Clear Selection";
+pub const LABEL_CLEAR_THUMBNAIL_CACHE: &str = "Clear Thumbnail Cache";
+pub const LABEL_CLIPBOARD_ALPHA: &str = "Transparency when copying images";
+pub const LABEL_CLIPBOARD_ALPHA_BLACK: &str = "Flatten onto black";
+pub const LABEL_CLIPBOARD_ALPHA_GRAY: &str = "Flatten onto gray";
+pub const LABEL_CLIPBOARD_ALPHA_KEEP: &str = "Keep";
+pub const LABEL_CLIPBOARD_ALPHA_WHITE: &str = "Flatten onto white";
+pub const LABEL_CLIPBOARD_WARN: &str = "Confirm copying images larger than";
+pub const LABEL_CLOSE: &str = "Close";
+pub const LABEL_COLLECTIONS: &str = "Collections";
+pub const LABEL_COLOR_TYPE: &str = "Save box art as";
+pub const LABEL_COLOR_TYPE_INDEXED: &str = "Indexed (256 colors, smallest)";
+pub const LABEL_COLOR_TYPE_ORIGINAL: &str = "Original color type";
+pub const LABEL_COLOR_TYPE_RGB8: &str = "RGB, 8-bit (no transparency)";
+pub const LABEL_COLOR_TYPE_RGBA8: &str = "RGBA, 8-bit";
+pub const LABEL_CONTRAST: &str = "Contrast";
 pub const LABEL_COPY: &str = "Copy";
+pub const LABEL_COPY_ANYWAY: &str = "Copy Anyway";
+pub const LABEL_COPY_DEBUG_INFO: &str = "Copy Debug Info";
 pub const LABEL_COPY_IMAGE: &str = "Copy Image";
+pub const LABEL_COPY_MISSING_NAMES: &str = "Copy Missing Names";
 pub const LABEL_COPY_PATH: &str = "Copy Path";
+pub const LABEL_COPY_REPORT: &str = "Copy Report";
+pub const LABEL_COPY_SUMMARY: &str = "Copy Summary";
+pub const LABEL_COPY_TO_MATCHING: &str = "Copy to Matching ROMs";
+pub const LABEL_CURRENT_ART: &str = "Current";
+pub const LABEL_DEFAULT_ART: &str = "Default art:";
+pub const LABEL_DEFAULT_ROM_ACTION: &str = "When a ROM is opened with Manage";
+pub const LABEL_DELETE: &str = "Delete";
+pub const LABEL_DELETED: &str = "Box art deleted";
+pub const LABEL_DELETE_ALL: &str = "Delete All";
+pub const LABEL_DELETE_SELECTED_ART: &str = "Delete Art of Selected";
+pub const LABEL_DENSITY: &str = "ROM list density";
+pub const LABEL_DENSITY_COMFORTABLE: &str = "Comfortable";
+pub const LABEL_DENSITY_COMPACT: &str = "Compact";
+pub const LABEL_DISCARD: &str = "Discard";
 pub const LABEL_DONE: &str = "Done";
+pub const LABEL_EDITOR_COMMAND: &str = "Image editor";
+pub const LABEL_EDITOR_COMMAND_HINT: &str = "Command used by Open in Editor, e.g. gimp. Quote parts that contain spaces. The box art path is added at the end. Leave empty to use the system's default image app.";
+pub const LABEL_EDITOR_COMMAND_PLACEHOLDER: &str = "System default";
+pub const LABEL_ERRORS_ALL: &str = "All errors";
+pub const LABEL_ERRORS_CLIPBOARD: &str = "Clipboard";
+pub const LABEL_ERRORS_DECODE: &str = "Unreadable images";
+pub const LABEL_ERRORS_FILESYSTEM: &str = "Files and folders";
+pub const LABEL_ERRORS_OTHER: &str = "Other";
+pub const LABEL_EXCLUDED_COLLECTIONS: &str = "Folders to skip when indexing";
+pub const LABEL_EXCLUDED_COLLECTIONS_HINT: &str = "Comma separated, * and ? match any text. Takes effect the next time the Roms folder is indexed.";
+pub const LABEL_EXCLUDED_COLLECTIONS_PLACEHOLDER: &str = "BIOS, Saves, *backup*";
+pub const LABEL_EXPECT_ART: &str = "Expect Art";
+pub const LABEL_EXPORT_SETTINGS: &str = "Export Settings";
+pub const LABEL_EXPORT_SETTINGS_HINT: &str =
+    "Save these settings to a file, to back them up or import them on another machine.";
+pub const LABEL_EXTENSION_SYSTEMS_PLACEHOLDER: &str = "sfc=SFC, gba=GBA";
+pub const LABEL_FETCH_ART: &str = "Fetch Art";
+pub const LABEL_FETCH_ART_FOR_SELECTED: &str = "Fetch Art for Selected";
+pub const LABEL_FILL_MISSING: &str = "Fill Missing";
+pub const LABEL_FILTERED: &str = "filtered";
+pub const LABEL_FILTER_ALL: &str = "All ROMs";
+pub const LABEL_FILTER_CATMULL_ROM: &str = "Catmull-Rom";
+pub const LABEL_FILTER_LANCZOS3: &str = "Lanczos3 (photos)";
+pub const LABEL_FILTER_NEAREST: &str = "Nearest (pixel art)";
+pub const LABEL_FILTER_TRIANGLE: &str = "Triangle";
+pub const LABEL_FILTER_UNVERIFIED: &str = "Not verified";
+pub const LABEL_FILTER_VERIFIED: &str = "Verified";
+pub const LABEL_FIND_ORPHANED_ART: &str = "Find Orphaned Art";
+pub const LABEL_FS_TIMEOUT: &str = "Give up on unresponsive files after";
+pub const LABEL_GROUP_BY_COLLECTION: &str = "Group by collection";
+pub const LABEL_GROUP_BY_EXTENSION: &str = "Group loose ROMs by file extension";
+pub const LABEL_GROUP_BY_EXTENSION_HINT: &str = "When the Roms folder holds ROMs directly, split them into one collection per system using these extension=system pairs. Takes effect the next time the Roms folder is indexed.";
+pub const LABEL_HIDDEN_BY_FILTER: &str = "more hidden by the filter are left out";
+pub const LABEL_IGNORE_MISSING: &str = "Ignore Missing";
+pub const LABEL_IMPORTING: &str = "Importing";
+pub const LABEL_IMPORT_RESIZE: &str = "Downscale imported art to fit";
+pub const LABEL_IMPORT_RESIZE_OFF: &str = "Off";
+pub const LABEL_IMPORT_SETTINGS: &str = "Import Settings";
+pub const LABEL_INDEXING_ERRORS: &str = "indexing errors";
+pub const LABEL_INFIX_SLOW_FS: &str = "' took ";
+pub const LABEL_JUST_NOW: &str = "just now";
+pub const LABEL_KEEP_PREVIOUS_ART: &str = "Keep replaced art next to the new art";
+pub const LABEL_KEEP_PREVIOUS_ART_HINT: &str = "Saves the art being replaced as {stem}.prev.png, overwriting any older one, so you can compare or restore it later. Undo works either way.";
+pub const LABEL_KEEP_REVIEWING: &str = "Keep Reviewing";
+pub const LABEL_LAYOUT_GALLERY: &str = "Gallery";
+pub const LABEL_LAYOUT_LIST: &str = "List";
+pub const LABEL_LOADING_IMAGE: &str = "Loading image...";
 pub const LABEL_MANAGE: &str = "Manage";
+pub const LABEL_MARK_VERIFIED: &str = "Mark Verified";
+pub const LABEL_MEDIA_FOLDERS: &str = "Create missing .media folders";
+pub const LABEL_MEDIA_FOLDERS_ON_FIRST_WRITE: &str = "On first write";
+pub const LABEL_MEDIA_FOLDERS_WHEN_INDEXING: &str = "When indexing";
+pub const LABEL_MERGE_CASE_DUPLICATES: &str = "Treat as One";
+pub const LABEL_MERGE_CASE_DUPLICATES_HINT: &str =
+    "Turning this off takes effect the next time the Roms folder is indexed.";
+pub const LABEL_MERGE_CASE_DUPLICATES_SETTING: &str =
+    "Treat collections whose names differ only in case as one";
+pub const LABEL_MIGRATE_BOXART_TEMPLATE: &str = "Rename Existing Art";
+pub const LABEL_MISSING_BOX_ART: &str = "missing box art";
+pub const LABEL_MODIFIED: &str = "modified";
+pub const LABEL_NAMES_AS_ON_DISK: &str = "As on disk";
+pub const LABEL_NAMES_WITHOUT_TAGS: &str = "Without tags like (USA) and [!]";
+pub const LABEL_NAME_NORMALIZATION: &str = "ROM names";
+pub const LABEL_NEVER: &str = "Never";
+pub const LABEL_NEW_ART: &str = "New";
+pub const LABEL_NEXT_MISSING: &str = "Next Missing";
+pub const LABEL_NOTE_PLACEHOLDER: &str = "Note, e.g. needs a better scan";
+pub const LABEL_NOT_IN_A_COLLECTION: &str = "Not about a collection";
 pub const LABEL_NO_BOX_ART: &str = "No box art";
+pub const LABEL_NO_COLLECTIONS_FOUND: &str = "No collections found";
 pub const LABEL_NO_ERRORS: &str = "All good! No errors encountered.";
 pub const LABEL_NO_IMAGE: &str = "No image";
+pub const LABEL_NO_MATCHING_ROMS: &str =
+    "No ROMs with the same name are missing art in other collections.";
 pub const LABEL_NO_ROM_SELECTED: &str = "No ROM selected";
+pub const LABEL_OF: &str = "of";
+pub const LABEL_OF_BOX_ART: &str = "of box art";
+pub const LABEL_ONLY_INCOMPLETE_COLLECTIONS: &str = "Only show collections missing box art";
 pub const LABEL_OPEN: &str = "Open";
+pub const LABEL_OPEN_IN_EDITOR: &str = "Open in Editor";
+pub const LABEL_ORPHANED_EXPLANATION: &str =
+    "These files are in media folders but don't belong to any ROM in your library.";
+pub const LABEL_ORPHANED_FILES: &str = "orphaned files";
+pub const LABEL_ORPHANS_MATCH_RENAMED_ROMS: &str =
+    "orphaned files look like the art of ROMs that were renamed:";
 pub const LABEL_PASTE_IMAGE: &str = "Paste Image";
-pub const LABEL_DELETE: &str = "Delete";
+pub const LABEL_PERSIST_THUMBNAILS: &str = "Save gallery thumbnails between launches";
+pub const LABEL_PERSIST_THUMBNAILS_HINT: &str = "Keeps thumbnails in the cache folder, so large collections open faster in the gallery next time.";
 pub const LABEL_PICK: &str = "Pick";
+pub const LABEL_PIN: &str = "Pin";
+pub const LABEL_PREFIX_ANIMATED_ART: &str = "This art is animated with";
+pub const LABEL_PREFIX_BULK_DELETE: &str = "This will delete";
+pub const LABEL_PREFIX_DISCARD_MATCHING_FILL: &str = "The art hasn't been applied to the";
+pub const LABEL_PREFIX_LARGE_CLIPBOARD_IMAGE: &str = "This image is ";
+pub const LABEL_PREFIX_MATCHING_ROMS: &str = "Apply this art to";
+pub const LABEL_PREFIX_NO_COLLECTIONS_HINT: &str = "No subfolders containing ROMs were found in '";
+pub const LABEL_PREFIX_SLOW_FS: &str = "Slow filesystem: '";
+pub const LABEL_PREVIEW_GUIDES: &str = "Guide lines over the preview";
+pub const LABEL_PREVIEW_GUIDES_CENTER: &str = "Center cross";
+pub const LABEL_PREVIEW_GUIDES_OFF: &str = "None";
+pub const LABEL_PREVIEW_GUIDES_THIRDS: &str = "Rule of thirds";
+pub const LABEL_PREVIEW_MAX_SIZE: &str = "Maximum box art preview size";
+pub const LABEL_READ_ART_DIMENSIONS: &str = "Show the dimensions of all box art";
+pub const LABEL_READ_ART_DIMENSIONS_HINT: &str = "Reads each image's size from its header while indexing, so art with the wrong resolution stands out in the ROM list. Slows down indexing on slow storage, and takes effect the next time the Roms folder is indexed.";
+pub const LABEL_READ_BUFFER_SIZE: &str = "Read buffer for box art (larger helps on network drives)";
+pub const LABEL_READ_GAMELISTS: &str = "Use gamelist.xml files";
+pub const LABEL_READ_GAMELISTS_HINT: &str = "Takes ROM names and box art paths from a collection's gamelist.xml, as kept by EmulationStation-style frontends, instead of the file names. Takes effect the next time the Roms folder is indexed.";
+pub const LABEL_READ_ONLY: &str = "Read-only mode";
+pub const LABEL_READ_ONLY_HINT: &str = "Never write to disk: box art, notes, media folders and settings are left untouched. Launching with --read-only turns this on for one session.";
+pub const LABEL_READ_ONLY_TOOLTIP: &str = "Disabled in read-only mode";
+pub const LABEL_RECENTLY_MODIFIED: &str = "Recently Modified";
+pub const LABEL_RECENT_CLIPBOARD: &str = "Recent clipboard images";
+pub const LABEL_RECLAIMED: &str = "Reclaimed";
+pub const LABEL_REDO: &str = "Redo";
+pub const LABEL_REINDEX: &str = "Reindex";
+pub const LABEL_REINDEXING: &str = "Reindexing...";
+pub const LABEL_RENAME: &str = "Rename";
+pub const LABEL_RENAME_TO_MATCH: &str = "Rename to Match";
+pub const LABEL_REPLACE: &str = "Replace";
+pub const LABEL_REPLACEMENT_ART: &str = "Replacement";
+pub const LABEL_RESCANNING: &str = "Rescanning...";
+pub const LABEL_RESCAN_COLLECTION: &str = "Rescan";
+pub const LABEL_RESET_ADJUSTMENTS: &str = "Reset Adjustments";
+pub const LABEL_RESIZE_FILTER: &str = "Resize filter quality";
 pub const LABEL_RESTART: &str = "Restart";
 pub const LABEL_ROMS: &str = "Roms";
+pub const LABEL_ROM_ACTION_CHOOSE_FILE: &str = "Choose an image file";
+pub const LABEL_ROM_ACTION_PASTE: &str = "Paste from the clipboard";
+pub const LABEL_ROM_ACTION_PREVIEW: &str = "Show its art";
+pub const LABEL_SAVE_ART: &str = "Save";
+pub const LABEL_SAVING: &str = "Saving...";
+pub const LABEL_SEARCH: &str = "Search ROMs";
+pub const LABEL_SELECTED: &str = "selected";
+pub const LABEL_SELECT_ALL_IN_VIEW: &str = "Select All in View";
+pub const LABEL_SETTINGS: &str = "Settings";
+pub const LABEL_SET_DEFAULT_ART: &str = "Set Default Art";
+pub const LABEL_SHORTCUT_BACK: &str = "Go back or close a menu";
+pub const LABEL_SHORTCUT_CHOOSE: &str = "Choose an image file for the selected ROM";
+pub const LABEL_SHORTCUT_FOCUS_NEXT: &str = "Move to the next text field";
+pub const LABEL_SHORTCUT_FOCUS_PREVIOUS: &str = "Move to the previous text field";
+pub const LABEL_SHORTCUT_HELP: &str = "Show or hide this list";
+pub const LABEL_SHORTCUT_NEXT_MISSING: &str = "Select the next ROM without art";
+pub const LABEL_SHORTCUT_PASTE: &str = "Paste an image onto the selected ROM";
+pub const LABEL_SHORTCUT_PREVIEW_GUIDES: &str = "Switch the guide lines over the preview";
+pub const LABEL_SHORTCUT_REDO: &str = "Redo";
+pub const LABEL_SHORTCUT_SEARCH: &str = "Search the ROM list";
+pub const LABEL_SHORTCUT_UNDO: &str = "Undo";
+pub const LABEL_SHOWING: &str = "Showing";
 pub const LABEL_SHOW_ERRORS: &str = "Show Errors";
-pub const LABEL_LOADING_IMAGE: &str = "Loading image...";
+pub const LABEL_SHOW_SKIPPED: &str = "Show Skipped Folders";
+pub const LABEL_SIZE_UNITS: &str = "Box art size units";
+pub const LABEL_SIZE_UNITS_BINARY: &str = "Binary (KiB, MiB)";
+pub const LABEL_SIZE_UNITS_DECIMAL: &str = "Decimal (KB, MB)";
+pub const LABEL_SKIPPED_EXPLANATION: &str =
+    "These folders were scanned but contained no ROM files, so they are not listed.";
+pub const LABEL_STALE_BOXART_PATHS: &str =
+    "ROMs still have box art paths from a previous file name template.";
+pub const LABEL_SUFFIX_ANIMATED_ART: &str =
+    "frames. Frontends usually show only the first frame, so a static image is safer.";
+pub const LABEL_SUFFIX_BULK_DELETE: &str = "box art files";
+pub const LABEL_SUFFIX_DISCARD_MATCHING_FILL: &str =
+    "matching ROMs yet. Leaving now discards the list.";
+pub const LABEL_SUFFIX_LARGE_CLIPBOARD_IMAGE: &str =
+    " pixels and may take a while to copy to the clipboard.";
+pub const LABEL_SUFFIX_MATCHING_ROMS: &str = "ROMs with the same name that have none:";
+pub const LABEL_SUFFIX_NO_COLLECTIONS_HINT: &str =
+    "'. Pick the Roms folder at the root of your SD card, which holds one folder per system.";
+pub const LABEL_SYSTEM_CATEGORIES: &str = "Collection categories";
+pub const LABEL_SYSTEM_CATEGORIES_HINT: &str = "Comma separated system=category pairs, used to guess the category of collections you haven't picked one for. The system is the tag at the end of a folder name, like GBA in Game Boy Advance (GBA).";
+pub const LABEL_SYSTEM_CATEGORIES_PLACEHOLDER: &str = "GBA=Handheld, MD=Console";
+pub const LABEL_THEME: &str = "Theme";
+pub const LABEL_THEME_DARK: &str = "Dark";
+pub const LABEL_THEME_HIGH_CONTRAST: &str = "High Contrast";
+pub const LABEL_THEME_LIGHT: &str = "Light";
+pub const LABEL_THEME_SYSTEM: &str = "Match System";
+pub const LABEL_THUMBNAIL_CACHE_LIMIT: &str = "Gallery thumbnails kept in memory";
+pub const LABEL_TRANSPARENCY_ANY: &str = "Either (don't check)";
+pub const LABEL_TRANSPARENCY_OPAQUE: &str = "Opaque";
+pub const LABEL_TRANSPARENCY_TRANSPARENT: &str = "Transparent";
+pub const LABEL_UI_SCALE: &str = "Interface size";
+pub const LABEL_UI_SCALE_LARGE: &str = "Large";
+pub const LABEL_UI_SCALE_MEDIUM: &str = "Medium";
+pub const LABEL_UI_SCALE_SMALL: &str = "Small";
+pub const LABEL_UNDO: &str = "Undo";
+pub const LABEL_UNIT_DAY: &str = "day";
+pub const LABEL_UNIT_HOUR: &str = "hour";
+pub const LABEL_UNIT_MINUTE: &str = "minute";
+pub const LABEL_UNIT_MONTH: &str = "month";
+pub const LABEL_UNIT_YEAR: &str = "year";
+pub const LABEL_UNLIMITED: &str = "Unlimited";
+pub const LABEL_UNMARK_VERIFIED: &str = "Unmark Verified";
+pub const LABEL_UNPIN: &str = "Unpin";
+pub const LABEL_VERIFIED: &str = "Verified";
+pub const LABEL_VERIFIED_MARK: &str = "✓";
 
 pub const UI_SETUP_INDEXING: &str = "Your collection is being indexed, please be patient.";
 pub const UI_SETUP_WELCOME: &str = "Welcome to NextArt, please provide the path to the Roms folder located at the root of your SD Card.";
 
+pub const UI_TITLE_ART_CHECK: &str = "Check All Art";
+pub const UI_TITLE_BULK_DELETE: &str = "Delete Box Art?";
+pub const UI_TITLE_COMPARE_ART: &str = "Replace Box Art?";
+pub const UI_TITLE_DISCARD_MATCHING_FILL: &str = "Discard Matching ROMs?";
 pub const UI_TITLE_ERROR: &str = "NextArt: Error";
 pub const UI_TITLE_ERRORS: &str = "Errors";
 pub const UI_TITLE_LOADING: &str = "NextArt: Loading...";
 pub const UI_TITLE_MAIN: &str = "NextArt";
+pub const UI_TITLE_NEW_ART: &str = "Save Box Art?";
+pub const UI_TITLE_ORPHANED_ART: &str = "Orphaned Art";
+pub const UI_TITLE_RECENTLY_MODIFIED: &str = "Modified This Session";
+pub const UI_TITLE_SETTINGS: &str = "Settings";
 pub const UI_TITLE_SETUP: &str = "NextArt: Setup";
+pub const UI_TITLE_SHORTCUTS: &str = "Keyboard Shortcuts";
+pub const UI_TITLE_SKIPPED: &str = "Skipped Folders";
+
+pub const CLI_USAGE_DUMP_INDEX: &str = "Usage: nextart --dump-index <roms_path>";
 
 pub const DIR_ORG: &str = "sysrqmagician";
 pub const DIR_APP: &str = "nextart";